@@ -2,14 +2,65 @@ extern crate configparser;
 use configparser::ini::Ini;
 
 use std::error::Error;
+use std::path::Path;
+
+use crate::sim::disorder::{
+    Disorder, DisorderKind, Distribution, DISORDER_NAMES, DISORDER_SCALE_NAMES, DISTRIBUTION_NAMES,
+};
+use crate::sim::flux::{FluxProfile, FLUX_NAMES};
+use crate::sim::initial::{InitialProfile, INITIAL_NAMES};
+use crate::sim::lattice::LatticeKind;
+use crate::sim::models::{gen_shapes, ModelKind, Shape};
+use crate::sim::schedule::{TimeSchedule, SCHEDULE_NAMES};
+use crate::sim::statistics::{SlopeErrors, SLOPE_ERROR_NAMES};
 
 #[derive(Debug)]
 pub struct InputParams {
-    pub lengths_t_max: Vec<(i32, i32)>,
+    pub models: Vec<ModelKind>,
+    pub lengths_t_max: Vec<(i32, f64)>,
     pub k_neighbours: Vec<i32>,
+    pub sticking_probs: Vec<Vec<f64>>,
     pub seeds: Vec<i32>,
+    pub schedule: TimeSchedule,
+    pub dimension: usize,
+    pub lattice: LatticeKind,
     pub periodic_bc: bool,
+    pub periodic_bc_y: bool,
     pub init_seed: i32,
+    pub track_bulk: bool,
+    pub stop_at_saturation: bool,
+    pub saturation_tolerance: f64,
+    pub saturation_decades: f64,
+    pub height_correlation: bool,
+    pub correlation_every: usize,
+    pub correlation_fraction: f64,
+    pub structure_factor: bool,
+    pub structure_every: usize,
+    pub height_histogram: bool,
+    pub histogram_times: Vec<f64>,
+    pub histogram_beta: f64,
+    pub histogram_bins: usize,
+    pub histogram_range: f64,
+    pub standard_errors: bool,
+    pub slope_errors: SlopeErrors,
+    pub slope_decades: f64,
+    pub desorption_fraction: f64,
+    pub desorption_bond_factor: f64,
+    pub flux: FluxProfile,
+    pub initial_profile: InitialProfile,
+    pub disorder: Disorder,
+    pub rsos_max_step: usize,
+    pub rsos_time_per_attempt: bool,
+    pub oblique_angle: f64,
+    pub oblique_spread: f64,
+    pub polyomino_shapes: Vec<Shape>,
+    pub polyomino_label: String,
+    pub mixture_models: [ModelKind; 2],
+    pub mixture_k_neighbours: [i32; 2],
+    pub mixture_p: f64,
+    pub dla_launch_margin: usize,
+    pub dla_kill_radius: usize,
+    pub diffusion_length: i32,
 }
 
 impl InputParams {
@@ -17,46 +68,342 @@ impl InputParams {
         // Instantiate our parameters for the simulation
         let config = open_config().expect("Failed to open `config.ini`.");
 
+        let model_names: Vec<String> =
+            parse_config_str_array(&config, "simulation_params", "models")
+                .expect("Failed to parse growth models.");
+        let models = gen_models(&model_names);
+
         let lengths: Vec<i32> =
             parse_config_array(&config, "simulation_params", "substrate_lengths")
                 .expect("Failed to parse substrate lengths.");
-        let lengths_t_max = gen_lengths_t_max(&lengths);
+        let t_max_names: Vec<String> =
+            parse_config_str_array(&config, "simulation_params", "t_max")
+                .expect("Failed to parse maximum times.");
+        let lengths_t_max = gen_lengths_t_max(&config, &lengths, &t_max_names);
 
         let k_neighbours: Vec<i32> =
             parse_config_array(&config, "simulation_params", "k_neighbours")
                 .expect("Failed to parse number of nearest neighbours.");
+        let sticking_names: Vec<String> =
+            parse_config_str_array(&config, "simulation_params", "sticking_probabilities")
+                .expect("Failed to parse sticking probabilities.");
+        let sticking_probs = gen_sticking_probs(&sticking_names);
         let seeds: Vec<i32> = parse_config_array(&config, "simulation_params", "seeds")
             .expect("Failed to parse number of seeds.");
+
+        // Schedule of times at which to measure the surface
+        let schedule_name: String = parse_config_str(&config, "schedule", "kind")
+            .expect("Failed to parse time schedule.")
+            .to_lowercase();
+        let schedule: TimeSchedule = gen_schedule(&config, &schedule_name);
+
+        let dimension: i32 = parse_config_i32(&config, "options", "dimension")
+            .expect("Failed to parse substrate dimension.");
+        let lattice_name: String = parse_config_str(&config, "options", "lattice")
+            .expect("Failed to parse substrate lattice.");
+        let lattice: LatticeKind = lattice_name
+            .to_lowercase()
+            .parse()
+            .unwrap_or_else(|err| panic!("Error: {}", err));
         let periodic_bc: bool = parse_config_option(&config, "options", "periodic_bc")
             .expect("Failed to parse whether to apply periodic boundary conditions.");
+        let periodic_bc_y: bool = parse_config_option(&config, "options", "periodic_bc_y")
+            .expect("Failed to parse whether to apply periodic boundary conditions along y.");
         let init_seed: i32 = parse_config_i32(&config, "options", "init_seed")
             .expect("Failed to parse initial random number seed.");
+        let track_bulk: bool = parse_config_option(&config, "options", "track_bulk")
+            .expect("Failed to parse whether to track the bulk of the deposit.");
+        let stop_at_saturation: bool =
+            parse_config_option(&config, "options", "stop_at_saturation")
+                .expect("Failed to parse whether to stop once the width saturates.");
+        let saturation_tolerance: f64 =
+            parse_config_f64(&config, "options", "saturation_tolerance")
+                .expect("Failed to parse tolerance for the width to count as saturated.");
+        let saturation_decades: f64 = parse_config_f64(&config, "options", "saturation_decades")
+            .expect("Failed to parse number of decades to run for after saturation.");
+        let height_correlation: bool =
+            parse_config_option(&config, "options", "height_correlation").expect(
+                "Failed to parse whether to measure the height-height correlation function.",
+            );
+        let correlation_every: i32 = parse_config_i32(&config, "options", "correlation_every")
+            .expect("Failed to parse how often to measure the height-height correlation function.");
+        let correlation_fraction: f64 =
+            parse_config_f64(&config, "options", "correlation_fraction")
+                .expect("Failed to parse fraction of 2 w^2 defining the correlation length.");
+        let structure_factor: bool = parse_config_option(&config, "options", "structure_factor")
+            .expect("Failed to parse whether to measure the structure factor.");
+        let structure_every: i32 = parse_config_i32(&config, "options", "structure_every")
+            .expect("Failed to parse how often to measure the structure factor.");
+        let height_histogram: bool = parse_config_option(&config, "options", "height_histogram")
+            .expect("Failed to parse whether to histogram the rescaled heights.");
+        let histogram_time_names: Vec<String> =
+            parse_config_str_array(&config, "options", "histogram_times")
+                .expect("Failed to parse times at which to histogram the rescaled heights.");
+        let histogram_beta: f64 = parse_config_f64(&config, "options", "histogram_beta")
+            .expect("Failed to parse growth exponent for rescaling the height histograms.");
+        let histogram_bins: i32 = parse_config_i32(&config, "options", "histogram_bins")
+            .expect("Failed to parse number of bins of the height histograms.");
+        let histogram_range: f64 = parse_config_f64(&config, "options", "histogram_range")
+            .expect("Failed to parse range of the height histograms.");
+        // The histogram options are only checked if the histograms are measured
+        let histogram_times: Vec<f64> = if height_histogram {
+            gen_histogram_times(
+                &histogram_time_names,
+                histogram_beta,
+                histogram_bins,
+                histogram_range,
+            )
+        } else {
+            Vec::new()
+        };
+        let standard_errors: bool = parse_config_option(&config, "options", "standard_errors")
+            .expect("Failed to parse whether to write the standard errors of the averages.");
+        let slope_error_name: String = parse_config_str(&config, "options", "slope_errors")
+            .expect("Failed to parse how to estimate the errors of the log-slopes.")
+            .to_lowercase();
+        let slope_errors: SlopeErrors = gen_slope_errors(&config, &slope_error_name);
+        let slope_decades: f64 = parse_config_f64(&config, "options", "slope_decades")
+            .expect("Failed to parse decades of time to fit each log-slope over.");
+        let desorption_fraction: f64 = parse_config_f64(&config, "options", "desorption_fraction")
+            .expect("Failed to parse fraction of events which are desorption attempts.");
+        let desorption_bond_factor: f64 =
+            parse_config_f64(&config, "options", "desorption_bond_factor")
+                .expect("Failed to parse bond factor for desorption.");
+
+        // Profile of the flux of particles across the substrate
+        let flux_name: String = parse_config_str(&config, "flux", "profile")
+            .expect("Failed to parse flux profile.")
+            .to_lowercase();
+        let flux: FluxProfile = gen_flux_profile(&config, &flux_name);
+
+        // Initial profile of the substrate
+        let initial_name: String = parse_config_str(&config, "initial_profile", "profile")
+            .expect("Failed to parse initial profile.")
+            .to_lowercase();
+        let initial_profile: InitialProfile = gen_initial_profile(&config, &initial_name);
+
+        // Quenched disorder in the substrate
+        let disorder_name: String = parse_config_str(&config, "disorder", "kind")
+            .expect("Failed to parse kind of quenched disorder.")
+            .to_lowercase();
+        let disorder: Disorder = gen_disorder(&config, &disorder_name);
+
+        // Options which only apply to specific growth models
+        let mixture_names: Vec<String> =
+            parse_config_str_array(&config, "model_options", "mixture_models")
+                .expect("Failed to parse species models for the mixture model.");
+        let mixture_k_neighbours: Vec<i32> =
+            parse_config_array(&config, "model_options", "mixture_k_neighbours")
+                .expect("Failed to parse species nearest neighbours for the mixture model.");
+        let mixture_p: f64 = parse_config_f64(&config, "model_options", "mixture_p")
+            .expect("Failed to parse probability of species A for the mixture model.");
+        // The species are only checked if a mixture is run, and are otherwise left as placeholders
+        let (mixture_models, mixture_k) = if models.contains(&ModelKind::Mixture) {
+            check_mixture_params(&mixture_k_neighbours, mixture_p);
+            (
+                gen_mixture_models(&mixture_names),
+                [mixture_k_neighbours[0], mixture_k_neighbours[1]],
+            )
+        } else {
+            ([ModelKind::Ballistic; 2], [0; 2])
+        };
+        let rsos_max_step: i32 = parse_config_i32(&config, "model_options", "rsos_max_step")
+            .expect("Failed to parse maximum step height for the RSOS model.");
+        let rsos_time: String = parse_config_str(&config, "model_options", "rsos_time")
+            .expect("Failed to parse definition of time for the RSOS model.");
+        let oblique_angle: f64 = parse_config_f64(&config, "model_options", "oblique_angle")
+            .expect("Failed to parse firing angle for the oblique model.");
+        let oblique_spread: f64 = parse_config_f64(&config, "model_options", "oblique_spread")
+            .expect("Failed to parse spread of firing angles for the oblique model.");
+        // Like the shapes below, these are only checked if the model is run, on its own or in a
+        //  mixture, so that they can't stop runs of other models
+        let rsos_time_per_attempt: bool = if selects(ModelKind::Rsos, &models, &mixture_models) {
+            check_rsos_params(rsos_max_step);
+            gen_rsos_time_per_attempt(&rsos_time.to_lowercase())
+        } else {
+            true
+        };
+        if selects(ModelKind::Oblique, &models, &mixture_models) {
+            check_oblique_angles(oblique_angle, oblique_spread);
+        }
+        let polyomino_names: Vec<String> =
+            parse_config_str_array(&config, "model_options", "polyomino_shapes")
+                .expect("Failed to parse shapes for the polyomino model.");
+        let polyomino_file: String = parse_config_str(&config, "model_options", "polyomino_file")
+            .expect("Failed to parse shape file for the polyomino model.");
+        // The shapes are only read if they are used, so a missing shape file can't stop other runs
+        let polyomino_shapes: Vec<Shape> =
+            if selects(ModelKind::Polyomino, &models, &mixture_models) {
+                gen_shapes(&polyomino_names, &polyomino_file)
+                    .unwrap_or_else(|err| panic!("Error: {}", err))
+            } else {
+                Vec::new()
+            };
+        let polyomino_label = gen_polyomino_label(&polyomino_names, &polyomino_file);
+        let dla_launch_margin: i32 =
+            parse_config_i32(&config, "model_options", "dla_launch_margin")
+                .expect("Failed to parse launch height margin for the DLA model.");
+        let dla_kill_radius: i32 = parse_config_i32(&config, "model_options", "dla_kill_radius")
+            .expect("Failed to parse kill radius for the DLA model.");
+        if models.contains(&ModelKind::Dla) {
+            check_dla_params(dla_launch_margin, dla_kill_radius);
+        }
+        let diffusion_length: i32 = parse_config_i32(&config, "model_options", "diffusion_length")
+            .expect("Failed to parse diffusion length for the MBE models.");
+        if diffusion_length < 0 {
+            panic!(
+                "Error: {} isn't a valid diffusion length! Please supply a non-negative integer.",
+                diffusion_length
+            );
+        }
+        check_dimension(dimension, &models, &mixture_models);
+        check_schedule(&schedule, &lengths_t_max);
+        check_saturation_params(saturation_tolerance, saturation_decades);
+        check_correlation_params(correlation_every, correlation_fraction);
+        if structure_every < 1 {
+            panic!("Error: measuring the structure factor every {} time points isn't valid! Please supply a positive integer.", structure_every);
+        }
+        check_error_params(standard_errors, slope_errors, slope_decades, &seeds);
+        check_desorption_params(desorption_fraction, desorption_bond_factor, &models);
+        check_flux(
+            &flux,
+            &lengths_t_max,
+            dimension as usize,
+            &models,
+            &mixture_models,
+            rsos_time_per_attempt,
+        );
+        check_initial_profile(&initial_profile, &lengths_t_max, dimension as usize);
+        check_disorder(&disorder, &models, &mixture_models);
 
         // Run-once to show the user the parameters about to be simulated
-        if displ_params == true {
+        if displ_params {
             println!(
                 r"
 Parsed config from `./config/config_sim.ini`...
     Running simulations for parameters:
+        Models = {:?},
         L = {:?},
+        t_max = {:?},
         k = {:?},
+        Sticking Probabilities = {:?},
         Seeds = {:?},
-        Periodic Boundary Conditions: {:?},
-        Initial Seed = {:?}
+        Time Schedule = {:?},
+        Dimension = {:?}, Lattice = {:?},
+        Periodic Boundary Conditions: {:?}, Periodic Along y: {:?},
+        Initial Seed = {:?},
+        Track Bulk = {:?},
+        Stop At Saturation = {:?}, Saturation Tolerance = {:?}, Saturation Decades = {:?},
+        Height Correlation = {:?}, Correlation Every = {:?}, Correlation Fraction = {:?},
+        Structure Factor = {:?}, Structure Every = {:?},
+        Height Histogram = {:?}, Histogram Times = {:?}, Histogram Beta = {:?},
+        Histogram Bins = {:?}, Histogram Range = {:?},
+        Standard Errors = {:?}, Slope Errors = {:?}, Slope Decades = {:?},
+        Desorption Fraction = {:?}, Desorption Bond Factor = {:?},
+        Flux Profile = {:?}, Initial Profile = {:?}, Disorder = {:?},
+        RSOS Max Step = {:?}, RSOS Time Per = {:?},
+        Oblique Angle = {:?}, Oblique Spread = {:?},
+        Polyominoes = {:?}, Polyomino File = {:?},
+        Mixture Models = {:?}, Mixture k = {:?}, Mixture p = {:?},
+        DLA Launch Margin = {:?}, DLA Kill Radius = {:?},
+        Diffusion Length = {:?}
                 ",
-                lengths, k_neighbours, seeds, periodic_bc, init_seed
+                model_names,
+                lengths,
+                t_max_names,
+                k_neighbours,
+                sticking_names,
+                seeds,
+                schedule_name,
+                dimension,
+                lattice_name,
+                periodic_bc,
+                periodic_bc_y,
+                init_seed,
+                track_bulk,
+                stop_at_saturation,
+                saturation_tolerance,
+                saturation_decades,
+                height_correlation,
+                correlation_every,
+                correlation_fraction,
+                structure_factor,
+                structure_every,
+                height_histogram,
+                histogram_time_names,
+                histogram_beta,
+                histogram_bins,
+                histogram_range,
+                standard_errors,
+                slope_errors,
+                slope_decades,
+                desorption_fraction,
+                desorption_bond_factor,
+                flux_name,
+                initial_name,
+                disorder_name,
+                rsos_max_step,
+                rsos_time,
+                oblique_angle,
+                oblique_spread,
+                polyomino_names,
+                polyomino_file,
+                mixture_names,
+                mixture_k_neighbours,
+                mixture_p,
+                dla_launch_margin,
+                dla_kill_radius,
+                diffusion_length
             );
         }
 
-        let params: InputParams = InputParams {
-            lengths_t_max: lengths_t_max,
-            k_neighbours: k_neighbours,
-            seeds: seeds,
-            periodic_bc: periodic_bc,
-            init_seed: init_seed,
-        };
-
-        params
+        InputParams {
+            models,
+            lengths_t_max,
+            k_neighbours,
+            sticking_probs,
+            seeds,
+            schedule,
+            dimension: dimension as usize,
+            lattice,
+            periodic_bc,
+            periodic_bc_y,
+            init_seed,
+            track_bulk,
+            stop_at_saturation,
+            saturation_tolerance,
+            saturation_decades,
+            height_correlation,
+            correlation_every: correlation_every as usize,
+            correlation_fraction,
+            structure_factor,
+            structure_every: structure_every as usize,
+            height_histogram,
+            histogram_times,
+            histogram_beta,
+            histogram_bins: histogram_bins as usize,
+            histogram_range,
+            standard_errors,
+            slope_errors,
+            slope_decades,
+            desorption_fraction,
+            desorption_bond_factor,
+            flux,
+            initial_profile,
+            disorder,
+            rsos_max_step: rsos_max_step as usize,
+            rsos_time_per_attempt,
+            oblique_angle,
+            oblique_spread,
+            polyomino_shapes,
+            polyomino_label,
+            mixture_models,
+            mixture_k_neighbours: mixture_k,
+            mixture_p,
+            dla_launch_margin: dla_launch_margin as usize,
+            dla_kill_radius: dla_kill_radius as usize,
+            diffusion_length,
+        }
     }
 }
 
@@ -90,6 +437,25 @@ pub fn parse_config_array(
     Ok(vals)
 }
 
+pub fn parse_config_str_array(
+    config: &Ini,
+    section: &str,
+    key: &str,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    // Parse the config.ini file for its specified key value pair.
+    // This code works for parsing a vector of strings only.
+    let config_entry = &config
+        .get(section, key)
+        .expect("Invalid section/key pair in `config.ini`.")[..]; // Convert to string literal
+
+    let vals: Vec<String> = config_entry
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .collect();
+
+    Ok(vals)
+}
+
 pub fn parse_config_option(config: &Ini, section: &str, key: &str) -> Result<bool, Box<dyn Error>> {
     // Parse the config.ini file for its specified key value pair.
     // This code works for parsing boolean values only.
@@ -101,8 +467,8 @@ pub fn parse_config_option(config: &Ini, section: &str, key: &str) -> Result<boo
     let aliases_false: Vec<&str> = vec!["false", "False", "f", "no", "No", "n"];
 
     let val: bool = match config_entry {
-        config_entry if aliases_true.iter().any(|&i| i == config_entry) => true,
-        config_entry if aliases_false.iter().any(|&i| i == config_entry) => false,
+        config_entry if aliases_true.contains(&config_entry) => true,
+        config_entry if aliases_false.contains(&config_entry) => false,
         _ => {
             println!(
                 r"WARNING: The entry for `{}, {}` is malformed. Setting value to `false` by default.
@@ -128,34 +494,609 @@ pub fn parse_config_i32(config: &Ini, section: &str, key: &str) -> Result<i32, B
     Ok(val)
 }
 
-fn gen_lengths_t_max(lengths: &Vec<i32>) -> Vec<(i32, i32)> {
-    // Only supports powers of 2 from 8 to 4096 at the moment
-    // TODO: include support for different substrate lengths?
+pub fn parse_config_f64(config: &Ini, section: &str, key: &str) -> Result<f64, Box<dyn Error>> {
+    // Parse the config.ini file for its specified key value pair.
+    // This code works for parsing f64 values only.
+    let config_entry = &config
+        .get(section, key)
+        .expect("Invalid section/key pair in config.ini.")[..]; // convert to string literal &str
+
+    let val: f64 = config_entry.parse()?;
+    Ok(val)
+}
+
+pub fn parse_config_str(config: &Ini, section: &str, key: &str) -> Result<String, Box<dyn Error>> {
+    // Parse the config.ini file for its specified key value pair.
+    // This code works for parsing a single string only.
+    let config_entry = config
+        .get(section, key)
+        .expect("Invalid section/key pair in config.ini.");
+
+    Ok(config_entry.trim().to_string())
+}
+
+// Whether `model` is run, either on its own or as one of the species in a mixture
+fn selects(model: ModelKind, models: &[ModelKind], mixture_models: &[ModelKind]) -> bool {
+    models.contains(&model)
+        || (models.contains(&ModelKind::Mixture) && mixture_models.contains(&model))
+}
+
+fn gen_models(names: &[String]) -> Vec<ModelKind> {
+    // Look up each model by its name, panicking and telling the user if the name isn't recognised
+    names
+        .iter()
+        .map(|name| name.parse().unwrap_or_else(|err| panic!("Error: {}", err)))
+        .collect()
+}
+
+fn gen_sticking_probs(names: &[String]) -> Vec<Vec<f64>> {
+    // Each entry is either a single probability for every neighbour distance, or a colon-separated
+    //  list of probabilities for distances 1, 2, 3, etc.
+    names
+        .iter()
+        .map(|name| {
+            let probs: Vec<f64> = name
+                .split(':')
+                .map(|p| p.trim().parse().unwrap_or(-1.0))
+                .collect();
+            if !probs.iter().all(|p| (0.0..=1.0).contains(p)) {
+                panic!("Error: {} isn't a valid sticking probability! Please supply values from 0 to 1 inclusive, separated by `:` for each neighbour distance.", name);
+            }
+            probs
+        })
+        .collect()
+}
+
+fn gen_rsos_time_per_attempt(rsos_time: &str) -> bool {
+    // Time in the RSOS model is counted either per attempted or per accepted deposition
+    match rsos_time {
+        "attempts" => true,
+        "accepted" => false,
+        _ => {
+            panic!("Error: {} isn't a valid definition of time for the RSOS model! Please supply one of `attempts` or `accepted`.", rsos_time);
+        }
+    }
+}
+
+fn check_rsos_params(max_step: i32) {
+    // With no step allowed, no column could ever grow, and negative steps would wrap when cast
+    if max_step < 1 {
+        panic!("Error: {} isn't a valid maximum step height for the RSOS model! Please supply a positive integer.", max_step);
+    }
+}
+
+fn check_oblique_angles(angle: f64, spread: f64) {
+    // Particles fired at or beyond the horizontal would never reach the surface
+    if spread < 0.0 || angle.abs() + spread >= 90.0 {
+        panic!("Error: oblique angles of {} +/- {} degrees aren't valid! Please supply angles strictly between -90 and 90 degrees, and a non-negative spread.", angle, spread);
+    }
+}
+
+fn gen_mixture_models(names: &[String]) -> [ModelKind; 2] {
+    // Exactly two species, neither of which can itself be a mixture
+    let models: Vec<ModelKind> = gen_models(names);
+    if models.len() != 2 || !models.iter().all(|model| model.can_mix()) {
+        panic!("Error: {:?} aren't valid species for the mixture model! Please supply two models other than `mixture`, `eden` or `dla`.", names);
+    }
+    [models[0], models[1]]
+}
+
+fn check_mixture_params(k_neighbours: &[i32], p: f64) {
+    if k_neighbours.len() != 2 {
+        panic!("Error: {:?} aren't valid nearest neighbours for the mixture model! Please supply one for each species.", k_neighbours);
+    }
+    if !(0.0..=1.0).contains(&p) {
+        panic!("Error: {} isn't a valid probability for the mixture model! Please supply a value from 0 to 1 inclusive.", p);
+    }
+}
+
+fn check_dla_params(launch_margin: i32, kill_radius: i32) {
+    // Walkers have to start above the deposit, and inside the kill radius
+    if launch_margin < 1 || kill_radius <= launch_margin {
+        panic!("Error: a launch margin of {} and kill radius of {} aren't valid for the DLA model! Please supply a positive launch margin, and a larger kill radius.", launch_margin, kill_radius);
+    }
+}
+
+fn gen_schedule(config: &Ini, name: &str) -> TimeSchedule {
+    // Only the options for the chosen schedule need to make sense
+    let parse_points_per_decade = || -> f64 {
+        let points_per_decade: f64 = parse_config_f64(config, "schedule", "points_per_decade")
+            .expect("Failed to parse number of time points per decade.");
+        if !(points_per_decade.is_finite() && points_per_decade > 0.0) {
+            panic!("Error: {} isn't a valid number of time points per decade! Please supply a positive value.", points_per_decade);
+        }
+        points_per_decade
+    };
+    let parse_interval = || -> f64 {
+        let interval: f64 = parse_config_f64(config, "schedule", "interval")
+            .expect("Failed to parse interval between time points.");
+        if !(interval.is_finite() && interval > 0.0) {
+            panic!("Error: {} isn't a valid interval between time points! Please supply a positive value.", interval);
+        }
+        interval
+    };
+
+    match name {
+        "legacy" => TimeSchedule::Legacy,
+        "log" => TimeSchedule::Log {
+            points_per_decade: parse_points_per_decade(),
+        },
+        "linear" => TimeSchedule::Linear {
+            interval: parse_interval(),
+        },
+        "explicit" => {
+            let names: Vec<String> = parse_config_str_array(config, "schedule", "times")
+                .expect("Failed to parse list of time points.");
+            let mut times: Vec<f64> = names
+                .iter()
+                .map(|name| name.parse().unwrap_or(-1.0))
+                .collect();
+            if !times.iter().all(|t| t.is_finite() && *t > 0.0) {
+                panic!("Error: {:?} aren't valid time points! Please supply a list of positive values.", names);
+            }
+            times.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            TimeSchedule::Explicit { times }
+        }
+        "hybrid" => TimeSchedule::Hybrid {
+            points_per_decade: parse_points_per_decade(),
+            interval: parse_interval(),
+        },
+        _ => {
+            panic!(
+                "Error: {} isn't a valid time schedule! Please supply one of {:?}.",
+                name, SCHEDULE_NAMES
+            );
+        }
+    }
+}
+
+fn check_schedule(schedule: &TimeSchedule, lengths_t_max: &[(i32, f64)]) {
+    // Explicit times beyond t_max are left out, so at least one has to be left for every run
+    if let TimeSchedule::Explicit { times } = schedule {
+        for &(l, t_max) in lengths_t_max {
+            if times[0] > t_max {
+                panic!("Error: none of the time points {:?} come before t_max = {} for L = {}! Please supply at least one time point up to t_max.", times, t_max, l);
+            }
+        }
+    }
+}
+
+fn check_saturation_params(tolerance: f64, decades: f64) {
+    if tolerance <= 0.0 || decades < 0.0 {
+        panic!("Error: a saturation tolerance of {} and {} decades after saturation aren't valid! Please supply a positive tolerance, and a non-negative number of decades.", tolerance, decades);
+    }
+}
+
+fn check_correlation_params(every: i32, fraction: f64) {
+    if every < 1 || !(fraction > 0.0 && fraction <= 1.0) {
+        panic!("Error: measuring the height-height correlation function every {} time points, with a correlation length at {} of 2 w^2, isn't valid! Please supply a positive integer, and a fraction above 0 up to 1.", every, fraction);
+    }
+}
+
+fn gen_histogram_times(names: &[String], beta: f64, bins: i32, range: f64) -> Vec<f64> {
+    let times: Vec<f64> = names
+        .iter()
+        .map(|name| name.parse().unwrap_or(-1.0))
+        .collect();
+    if !times.iter().all(|t| t.is_finite() && *t > 0.0) {
+        panic!(
+            "Error: {:?} aren't valid times for the height histograms! Please supply a list of positive values.",
+            names
+        );
+    }
+    if !(beta.is_finite() && beta >= 0.0) || bins < 1 || !(range.is_finite() && range > 0.0) {
+        panic!("Error: a growth exponent of {}, {} bins and a range of {} aren't valid for the height histograms! Please supply a non-negative exponent, a positive integer number of bins, and a positive range.", beta, bins, range);
+    }
+    times
+}
+
+fn gen_slope_errors(config: &Ini, name: &str) -> SlopeErrors {
+    match name {
+        "none" => SlopeErrors::None,
+        "jackknife" => SlopeErrors::Jackknife,
+        "bootstrap" => {
+            let samples: i32 = parse_config_i32(config, "options", "bootstrap_samples")
+                .expect("Failed to parse number of bootstrap samples.");
+            if samples < 2 {
+                panic!("Error: {} isn't a valid number of bootstrap samples! Please supply an integer of at least 2.", samples);
+            }
+            SlopeErrors::Bootstrap {
+                samples: samples as usize,
+            }
+        }
+        _ => {
+            panic!(
+                "Error: {} isn't a valid way to estimate the errors of the log-slopes! Please supply one of {:?}.",
+                name, SLOPE_ERROR_NAMES
+            );
+        }
+    }
+}
+
+fn check_error_params(
+    standard_errors: bool,
+    slope_errors: SlopeErrors,
+    slope_decades: f64,
+    seeds: &[i32],
+) {
+    if !(slope_decades.is_finite() && slope_decades > 0.0) {
+        panic!("Error: fitting log-slopes over {} decades of time isn't valid! Please supply a positive value.", slope_decades);
+    }
+    // The spread across the seeds needs at least two of them
+    if (standard_errors || slope_errors != SlopeErrors::None) && seeds.iter().any(|&n| n < 2) {
+        panic!("Error: errors can't be estimated from ensembles of {:?} seeds! Please supply at least 2 seeds, or turn off `standard_errors` and `slope_errors`.", seeds);
+    }
+}
+
+fn check_desorption_params(fraction: f64, bond_factor: f64, models: &[ModelKind]) {
+    if !(0.0..1.0).contains(&fraction) || !(0.0..=1.0).contains(&bond_factor) {
+        panic!("Error: a desorption fraction of {} and bond factor of {} aren't valid! Please supply a fraction from 0 up to (but not including) 1, and a bond factor from 0 to 1 inclusive.", fraction, bond_factor);
+    }
+    // Models with their own record of the deposit wouldn't know about the particles removed
+    if fraction > 0.0 {
+        for model in models {
+            if model.keeps_own_record() {
+                panic!(
+                    "Error: the `{}` model doesn't support desorption!",
+                    model.name()
+                );
+            }
+        }
+    }
+}
+
+fn gen_flux_profile(config: &Ini, name: &str) -> FluxProfile {
+    // Only the options for the chosen profile need to make sense
+    match name {
+        "uniform" => FluxProfile::Uniform,
+        "gaussian" => {
+            let centre: f64 = parse_config_f64(config, "flux", "centre")
+                .expect("Failed to parse centre of the Gaussian flux.");
+            let width: f64 = parse_config_f64(config, "flux", "width")
+                .expect("Failed to parse width of the Gaussian flux.");
+            if !(0.0..=1.0).contains(&centre) || width <= 0.0 {
+                panic!("Error: a centre of {} and width of {} aren't valid for a Gaussian flux! Please supply a centre from 0 to 1 inclusive, and a positive width.", centre, width);
+            }
+            FluxProfile::Gaussian { centre, width }
+        }
+        "gradient" => {
+            let gradient: f64 = parse_config_f64(config, "flux", "gradient")
+                .expect("Failed to parse slope of the flux gradient.");
+            if !(0.0..=1.0).contains(&gradient) {
+                panic!("Error: {} isn't a valid flux gradient! Please supply a value from 0 to 1 inclusive.", gradient);
+            }
+            FluxProfile::Gradient { gradient }
+        }
+        "mask" => {
+            let period: i32 = parse_config_i32(config, "flux", "period")
+                .expect("Failed to parse period of the flux mask.");
+            let duty: f64 = parse_config_f64(config, "flux", "duty")
+                .expect("Failed to parse open fraction of the flux mask.");
+            let level: f64 = parse_config_f64(config, "flux", "mask_level")
+                .expect("Failed to parse flux through the closed part of the mask.");
+            if period < 1 || duty <= 0.0 || duty > 1.0 || !(0.0..=1.0).contains(&level) {
+                panic!("Error: a period of {}, duty of {} and mask level of {} aren't valid for a flux mask! Please supply a positive period, a duty above 0 up to 1, and a mask level from 0 to 1 inclusive.", period, duty, level);
+            }
+            FluxProfile::Mask {
+                period: period as usize,
+                duty,
+                level,
+            }
+        }
+        "file" => {
+            let file: String = parse_config_str(config, "flux", "file")
+                .expect("Failed to parse flux profile file.");
+            FluxProfile::from_file(&file).unwrap_or_else(|err| {
+                panic!("Error: failed to read flux profile {}: {}", file, err)
+            })
+        }
+        _ => {
+            panic!(
+                "Error: {} isn't a valid flux profile! Please supply one of {:?}.",
+                name, FLUX_NAMES
+            );
+        }
+    }
+}
+
+fn check_flux(
+    flux: &FluxProfile,
+    lengths_t_max: &[(i32, f64)],
+    dimension: usize,
+    models: &[ModelKind],
+    mixture_models: &[ModelKind],
+    rsos_time_per_attempt: bool,
+) {
+    // Every substrate needs one non-negative weight per column, and somewhere for particles to land
+    for &(l, _t_max) in lengths_t_max {
+        if let Some(weights) = flux.weights(l, dimension) {
+            let sites: usize = (l as usize).pow(dimension as u32);
+            if weights.len() != sites {
+                panic!("Error: the flux profile has {} weights, but an L = {} substrate has {} columns! Please supply one weight per column.", weights.len(), l, sites);
+            }
+            if !weights.iter().all(|w| w.is_finite() && *w >= 0.0)
+                || weights.iter().sum::<f64>() <= 0.0
+            {
+                panic!("Error: the flux profile isn't valid for an L = {} substrate! Please supply non-negative weights which aren't all 0.", l);
+            }
+            // Columns with no flux never grow, so once the rest reach the maximum step above them,
+            //  RSOS would reject every particle forever waiting for one to be accepted
+            if !rsos_time_per_attempt
+                && selects(ModelKind::Rsos, models, mixture_models)
+                && weights.contains(&0.0)
+            {
+                panic!("Error: the `rsos` model can't count time per `accepted` particle when some columns of an L = {} substrate get no flux, as it would end up rejecting every particle! Please give every column some flux, or count time per `attempts`.", l);
+            }
+        }
+    }
+
+    // Mixtures can only follow the flux if both of their species can
+    if *flux != FluxProfile::Uniform {
+        let mut checked: Vec<ModelKind> = models.to_vec();
+        if models.contains(&ModelKind::Mixture) {
+            checked.extend(mixture_models);
+        }
+        for model in checked {
+            if !model.supports_flux() {
+                panic!(
+                    "Error: the `{}` model doesn't support non-uniform flux!",
+                    model.name()
+                );
+            }
+        }
+    }
+}
+
+fn gen_initial_profile(config: &Ini, name: &str) -> InitialProfile {
+    // Only the options for the chosen profile need to make sense
+    let parse_shape = || -> (f64, f64) {
+        let amplitude: f64 = parse_config_f64(config, "initial_profile", "amplitude")
+            .expect("Failed to parse amplitude of the initial profile.");
+        let period: f64 = parse_config_f64(config, "initial_profile", "period")
+            .expect("Failed to parse period of the initial profile.");
+        if amplitude < 0.0 || period <= 0.0 {
+            panic!("Error: an amplitude of {} and period of {} aren't valid for the initial profile! Please supply a non-negative amplitude and a positive period.", amplitude, period);
+        }
+        (amplitude, period)
+    };
+
+    match name {
+        "flat" => InitialProfile::Flat,
+        "steps" => {
+            let (amplitude, period) = parse_shape();
+            InitialProfile::Steps { amplitude, period }
+        }
+        "sinusoid" => {
+            let (amplitude, period) = parse_shape();
+            InitialProfile::Sinusoid { amplitude, period }
+        }
+        "groove" => {
+            let (amplitude, period) = parse_shape();
+            InitialProfile::Groove { amplitude, period }
+        }
+        "rough" => {
+            let alpha: f64 = parse_config_f64(config, "initial_profile", "alpha")
+                .expect("Failed to parse roughness exponent of the initial profile.");
+            let amplitude: f64 = parse_config_f64(config, "initial_profile", "amplitude")
+                .expect("Failed to parse amplitude of the initial profile.");
+            if !(0.0..=1.0).contains(&alpha) || amplitude < 0.0 {
+                panic!("Error: a roughness exponent of {} and amplitude of {} aren't valid for a rough initial profile! Please supply an exponent from 0 to 1 inclusive, and a non-negative amplitude.", alpha, amplitude);
+            }
+            InitialProfile::Rough { alpha, amplitude }
+        }
+        "file" => {
+            let file: String = parse_config_str(config, "initial_profile", "file")
+                .expect("Failed to parse initial profile file.");
+            InitialProfile::from_file(&file).unwrap_or_else(|err| {
+                panic!("Error: failed to read initial profile {}: {}", file, err)
+            })
+        }
+        _ => {
+            panic!(
+                "Error: {} isn't a valid initial profile! Please supply one of {:?}.",
+                name, INITIAL_NAMES
+            );
+        }
+    }
+}
+
+fn check_initial_profile(profile: &InitialProfile, lengths_t_max: &[(i32, f64)], dimension: usize) {
+    // A profile read from a file needs one height per column of every substrate
+    if let InitialProfile::File { heights, .. } = profile {
+        for &(l, _t_max) in lengths_t_max {
+            let sites: usize = (l as usize).pow(dimension as u32);
+            if heights.len() != sites {
+                panic!("Error: the initial profile has {} heights, but an L = {} substrate has {} columns! Please supply one height per column.", heights.len(), l, sites);
+            }
+        }
+        if !heights.iter().all(|h| h.is_finite()) {
+            panic!("Error: the initial profile isn't valid! Please supply finite heights.");
+        }
+    }
+}
+
+fn gen_disorder(config: &Ini, name: &str) -> Disorder {
+    let kind: DisorderKind = match name {
+        "none" => return Disorder::none(),
+        "sticking" => DisorderKind::Sticking,
+        "growth" => DisorderKind::Growth,
+        _ => {
+            panic!(
+                "Error: {} isn't a valid kind of quenched disorder! Please supply one of {:?}.",
+                name, DISORDER_NAMES
+            );
+        }
+    };
+
+    let scale: String = parse_config_str(config, "disorder", "scale")
+        .expect("Failed to parse scale of the quenched disorder.")
+        .to_lowercase();
+    let per_site: bool = match &scale[..] {
+        "column" => false,
+        "site" if kind == DisorderKind::Sticking => true,
+        "site" => panic!("Error: growth rate disorder can only be drawn per column! Please supply a `scale` of `column`."),
+        _ => {
+            panic!(
+                "Error: {} isn't a valid scale of quenched disorder! Please supply one of {:?}.",
+                scale, DISORDER_SCALE_NAMES
+            );
+        }
+    };
+
+    // Sticking strengths are probabilities, whereas growth rates only have to be positive, so that
+    //  there's always somewhere for particles to land
+    let distribution_name: String = parse_config_str(config, "disorder", "distribution")
+        .expect("Failed to parse distribution of the quenched disorder.")
+        .to_lowercase();
+    let low: f64 = parse_config_f64(config, "disorder", "low")
+        .expect("Failed to parse lowest value of the quenched disorder.");
+    let high: f64 = parse_config_f64(config, "disorder", "high")
+        .expect("Failed to parse highest value of the quenched disorder.");
+    let valid: bool = match kind {
+        DisorderKind::Sticking => 0.0 <= low && low <= high && high <= 1.0,
+        _ => 0.0 < low && low <= high,
+    };
+    if !valid {
+        panic!("Error: values from {} to {} aren't valid for {} disorder! Please supply a low value no more than the high value, with both from 0 to 1 inclusive for sticking strengths, or both positive for growth rates.", low, high, name);
+    }
+    let distribution: Distribution = match &distribution_name[..] {
+        "uniform" => Distribution::Uniform { low, high },
+        "bimodal" => {
+            let fraction: f64 = parse_config_f64(config, "disorder", "fraction")
+                .expect("Failed to parse fraction of high values of the quenched disorder.");
+            if !(0.0..=1.0).contains(&fraction) {
+                panic!("Error: {} isn't a valid fraction for bimodal disorder! Please supply a value from 0 to 1 inclusive.", fraction);
+            }
+            Distribution::Bimodal {
+                low,
+                high,
+                fraction,
+            }
+        }
+        _ => {
+            panic!(
+                "Error: {} isn't a valid distribution of quenched disorder! Please supply one of {:?}.",
+                distribution_name, DISTRIBUTION_NAMES
+            );
+        }
+    };
+
+    Disorder::new(kind, per_site, distribution)
+}
+
+fn check_disorder(disorder: &Disorder, models: &[ModelKind], mixture_models: &[ModelKind]) {
+    // Growth rates act on the flux, so need models which follow it. Mixtures can only follow the
+    //  flux if both of their species can
+    let mut checked: Vec<ModelKind> = models.to_vec();
+    if models.contains(&ModelKind::Mixture) {
+        checked.extend(mixture_models);
+    }
+    if disorder.kind() == DisorderKind::Growth {
+        for &model in &checked {
+            if !model.supports_flux() {
+                panic!(
+                    "Error: the `{}` model doesn't support growth rate disorder!",
+                    model.name()
+                );
+            }
+        }
+    }
+    // Sticking strengths are only read by models which stick by chance, so any other model would
+    //  silently ignore them
+    if disorder.kind() == DisorderKind::Sticking {
+        for &model in &checked {
+            if !model.supports_sticking_disorder() {
+                panic!(
+                    "Error: the `{}` model doesn't support sticking disorder! Please only select `ballistic`, or a `mixture` of it.",
+                    model.name()
+                );
+            }
+        }
+    }
+}
 
-    // Create an empty vector for zipping together length and t_max
-    let mut lengths_with_t_max: Vec<(i32, i32)> = Vec::new(); // vec![(0, 0); lengths.len()];
+fn check_dimension(dimension: i32, models: &[ModelKind], mixture_models: &[ModelKind]) {
+    if dimension != 1 && dimension != 2 {
+        panic!(
+            "Error: {} isn't a valid substrate dimension! Please supply 1 or 2.",
+            dimension
+        );
+    }
+    // Mixtures can only run if both of their species can
+    let mut checked: Vec<ModelKind> = models.to_vec();
+    if models.contains(&ModelKind::Mixture) {
+        checked.extend(mixture_models);
+    }
+    for model in checked {
+        if !model.supports_dimension(dimension as usize) {
+            panic!(
+                "Error: the `{}` model doesn't support {}D substrates!",
+                model.name(),
+                dimension
+            );
+        }
+    }
+}
 
+fn gen_polyomino_label(names: &[String], file: &str) -> String {
+    // Label the set of polyominoes in the output filenames by their names, plus the name of the shape
+    //  file if there is one
+    let mut labels: Vec<&str> = names
+        .iter()
+        .map(|name| &name[..])
+        .filter(|name| !name.is_empty())
+        .collect();
+    if !file.is_empty() {
+        labels.push(
+            Path::new(file)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("file"),
+        );
+    }
+    labels.join("-")
+}
+
+fn gen_lengths_t_max(config: &Ini, lengths: &[i32], t_max_names: &[String]) -> Vec<(i32, f64)> {
+    // Any positive substrate length is accepted
     for length in lengths {
-        let t_max: i32 = match *length {
-            8 => 10_000,
-            16 => 10_000,
-            32 => 10_000,
-            64 => 10_000,
-            128 => 10_000,
-            256 => 10_000,
-            512 => 10_000,
-            1024 => 100_000,
-            2048 => 100_000,
-            4096 => 100_000,
-            8192 => 1_000_000,
-            16384 => 1_000_000,
-
-            // If length isn't one of the hard-coded values, panic and tell the user
+        if *length < 1 {
+            panic!(
+                "Error: {} isn't a valid substrate length! Please supply a positive integer.",
+                length
+            );
+        }
+    }
+
+    // Either t_max = c * L^z, or explicit values, which are either one for every length or one per
+    //  length
+    let t_maxes: Vec<f64> = if t_max_names == ["scaling"] {
+        let c: f64 = parse_config_f64(config, "simulation_params", "t_max_c")
+            .expect("Failed to parse prefactor for the maximum time.");
+        let z: f64 = parse_config_f64(config, "simulation_params", "t_max_z")
+            .expect("Failed to parse exponent for the maximum time.");
+        lengths.iter().map(|&l| c * (l as f64).powf(z)).collect()
+    } else {
+        let values: Vec<f64> = t_max_names
+            .iter()
+            .map(|name| name.parse().unwrap_or(-1.0))
+            .collect();
+        match values.len() {
+            1 => vec![values[0]; lengths.len()],
+            n if n == lengths.len() => values,
             _ => {
-                panic!("Error: {length} isn't a valid substrate length! Please supply a power of 2 from 8 to 16384 inclusive.", length = *length);
+                panic!("Error: {:?} aren't valid maximum times for substrate lengths {:?}! Please supply a single value, one value per substrate length, or `scaling`.", t_max_names, lengths);
             }
-        };
-        lengths_with_t_max.push((*length, t_max));
+        }
+    };
+
+    for (length, t_max) in lengths.iter().zip(&t_maxes) {
+        if !(t_max.is_finite() && *t_max > 0.0) {
+            panic!(
+                "Error: {} isn't a valid maximum time for L = {}! Please supply a positive value.",
+                t_max, length
+            );
+        }
     }
-    lengths_with_t_max
+    lengths.iter().copied().zip(t_maxes).collect()
 }
@@ -23,18 +23,28 @@ fn main() -> Result<(), Box<dyn Error>> {
     let params = InputParams::new(true);
 
     // Iterate through parameters parsed in the `.ini` and run simulations
-    for (lengths_t_max, k_neighbour, max_seed) in
-        iproduct!(params.lengths_t_max, params.k_neighbours, params.seeds)
-    {
+    for (model, lengths_t_max, k_neighbour, sticking_probs, max_seed) in iproduct!(
+        params.models,
+        params.lengths_t_max,
+        params.k_neighbours,
+        params.sticking_probs,
+        params.seeds
+    ) {
         // Re-parse the params so we're not trying to use a moved value every loop
         //  Wasteful? Probably. But insignificant compared to the runtime of the simulations
         let params = InputParams::new(false);
 
-        let current_params: SimulationParams =
-            SimulationParams::new(params, lengths_t_max, k_neighbour, max_seed);
+        let current_params: SimulationParams = SimulationParams::new(
+            params,
+            model,
+            lengths_t_max,
+            k_neighbour,
+            sticking_probs,
+            max_seed,
+        );
 
         let now = Instant::now();
-        sim::run(current_params)?;
+        sim::run_model(current_params)?;
         let new_now = Instant::now();
         println!(
             r"Done! Took {:?}
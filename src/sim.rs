@@ -3,14 +3,61 @@ Library file used for running the ballistic deposition simulations.
 */
 use num::{FromPrimitive, ToPrimitive};
 
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::iter::Sum;
 
+// The RNG is kept as it was ported from Numerical Recipes
+#[allow(clippy::redundant_field_names, clippy::needless_late_init)]
 mod random;
 use random::Ran2Generator;
 
 mod csv_writer;
-use csv_writer::write_csv;
+use csv_writer::{
+    write_correlation_csv, write_csv, write_histogram_csv, write_metadata, write_structure_csv,
+    write_voids_csv,
+};
+
+mod surface;
+use surface::Surface;
+
+pub mod lattice;
+use lattice::{Lattice, LatticeKind};
+
+mod desorption;
+use desorption::Desorption;
+
+pub mod flux;
+use flux::FluxProfile;
+
+pub mod initial;
+use initial::InitialProfile;
+
+pub mod disorder;
+use disorder::Disorder;
+
+pub mod schedule;
+use schedule::TimeSchedule;
+
+pub mod statistics;
+use statistics::{log_slope, slope_errors, standard_error, SlopeErrors};
+
+mod correlation;
+use correlation::{correlation_length, height_correlation};
+
+mod fft;
+
+mod structure;
+use structure::structure_factor;
+
+mod histogram;
+use histogram::Histogram;
+
+pub mod models;
+use models::{
+    Ballistic, DasSarma, Dla, Eden, Family, GrowthModel, Mixture, ModelKind, Oblique, Polyomino,
+    RandomDeposition, Rsos, Shape, WolfVillain,
+};
 
 use crate::ini_parser::InputParams;
 
@@ -21,30 +68,447 @@ Structs
 */
 
 // Struct containing all the parameters to be used
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SimulationParams {
-    length_t_max: (i32, i32),
+    model: ModelKind,
+    length_t_max: (i32, f64),
     k_neighbour: i32,
+    sticking_probs: Vec<f64>,
     max_seed: i32,
+    schedule: TimeSchedule,
+    dimension: usize,
+    lattice: LatticeKind,
     periodic_bc: bool,
+    periodic_bc_y: bool,
     init_seed: i32,
+    track_bulk: bool,
+    stop_at_saturation: bool,
+    saturation_tolerance: f64,
+    saturation_decades: f64,
+    height_correlation: bool,
+    correlation_every: usize,
+    correlation_fraction: f64,
+    structure_factor: bool,
+    structure_every: usize,
+    height_histogram: bool,
+    histogram_times: Vec<f64>,
+    histogram_beta: f64,
+    histogram_bins: usize,
+    histogram_range: f64,
+    standard_errors: bool,
+    slope_errors: SlopeErrors,
+    slope_decades: f64,
+    desorption_fraction: f64,
+    desorption_bond_factor: f64,
+    flux: FluxProfile,
+    initial_profile: InitialProfile,
+    disorder: Disorder,
+    rsos_max_step: usize,
+    rsos_time_per_attempt: bool,
+    oblique_angle: f64,
+    oblique_spread: f64,
+    polyomino_shapes: Vec<Shape>,
+    polyomino_label: String,
+    mixture_models: [ModelKind; 2],
+    mixture_k_neighbours: [i32; 2],
+    mixture_p: f64,
+    dla_launch_margin: usize,
+    dla_kill_radius: usize,
+    diffusion_length: i32,
 }
 
 impl SimulationParams {
     pub fn new(
         total_params: InputParams,
-        length_t_max: (i32, i32),
+        model: ModelKind,
+        length_t_max: (i32, f64),
         k_neighbour: i32,
+        sticking_probs: Vec<f64>,
         max_seed: i32,
     ) -> SimulationParams {
-        let current_params: SimulationParams = SimulationParams {
-            length_t_max: length_t_max,
-            k_neighbour: k_neighbour,
-            max_seed: max_seed,
+        SimulationParams {
+            model,
+            length_t_max,
+            k_neighbour,
+            sticking_probs,
+            max_seed,
+            schedule: total_params.schedule,
+            dimension: total_params.dimension,
+            lattice: total_params.lattice,
             periodic_bc: total_params.periodic_bc,
+            periodic_bc_y: total_params.periodic_bc_y,
             init_seed: total_params.init_seed,
-        };
-        current_params
+            track_bulk: total_params.track_bulk,
+            stop_at_saturation: total_params.stop_at_saturation,
+            saturation_tolerance: total_params.saturation_tolerance,
+            saturation_decades: total_params.saturation_decades,
+            height_correlation: total_params.height_correlation,
+            correlation_every: total_params.correlation_every,
+            correlation_fraction: total_params.correlation_fraction,
+            structure_factor: total_params.structure_factor,
+            structure_every: total_params.structure_every,
+            height_histogram: total_params.height_histogram,
+            histogram_times: total_params.histogram_times,
+            histogram_beta: total_params.histogram_beta,
+            histogram_bins: total_params.histogram_bins,
+            histogram_range: total_params.histogram_range,
+            standard_errors: total_params.standard_errors,
+            slope_errors: total_params.slope_errors,
+            slope_decades: total_params.slope_decades,
+            desorption_fraction: total_params.desorption_fraction,
+            desorption_bond_factor: total_params.desorption_bond_factor,
+            flux: total_params.flux,
+            initial_profile: total_params.initial_profile,
+            disorder: total_params.disorder,
+            rsos_max_step: total_params.rsos_max_step,
+            rsos_time_per_attempt: total_params.rsos_time_per_attempt,
+            oblique_angle: total_params.oblique_angle,
+            oblique_spread: total_params.oblique_spread,
+            polyomino_shapes: total_params.polyomino_shapes,
+            polyomino_label: total_params.polyomino_label,
+            mixture_models: total_params.mixture_models,
+            mixture_k_neighbours: total_params.mixture_k_neighbours,
+            mixture_p: total_params.mixture_p,
+            dla_launch_margin: total_params.dla_launch_margin,
+            dla_kill_radius: total_params.dla_kill_radius,
+            diffusion_length: total_params.diffusion_length,
+        }
+    }
+
+    // Total number of columns on the substrate
+    pub fn sites(&self) -> usize {
+        let (l, _t_max) = self.length_t_max;
+        (l as usize).pow(self.dimension as u32)
+    }
+
+    // Label for the substrate in the output filenames, e.g. `L512` or `L64x64_square`
+    pub fn substrate_label(&self) -> String {
+        let (l, _t_max) = self.length_t_max;
+        match self.dimension {
+            1 => format!("L{}", l),
+            _ => format!("L{}x{}_{}", l, l, self.lattice.name()),
+        }
+    }
+
+    // Label for the boundary conditions in the output filenames, with one digit per axis
+    pub fn boundary_label(&self) -> String {
+        [self.periodic_bc, self.periodic_bc_y][..self.dimension]
+            .iter()
+            .map(|&periodic| if periodic { "1" } else { "0" })
+            .collect()
+    }
+
+    // Label for the sticking probabilities in the output filenames, e.g. `1` or `1-0.5`
+    pub fn sticking_label(&self) -> String {
+        self.sticking_probs
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<String>>()
+            .join("-")
+    }
+
+    // Label for desorption in the output filenames, e.g. `_des0.1_b0.5`, which is left out when
+    //  particles never desorb
+    pub fn desorption_label(&self) -> String {
+        if self.desorption_fraction > 0.0 {
+            format!(
+                "_des{}_b{}",
+                self.desorption_fraction, self.desorption_bond_factor
+            )
+        } else {
+            String::new()
+        }
+    }
+
+    // Parameters of the run, as (name, value) pairs for the run metadata. This includes the initial
+    //  profile, which the filename only labels
+    pub fn metadata(&self) -> Vec<(String, String)> {
+        let (l, t_max) = self.length_t_max;
+        let mut metadata: Vec<(String, String)> = vec![
+            ("model".to_string(), self.model_label()),
+            ("substrate_length".to_string(), l.to_string()),
+            ("dimension".to_string(), self.dimension.to_string()),
+            ("lattice".to_string(), self.lattice.name().to_string()),
+            ("t_max".to_string(), t_max.to_string()),
+            ("schedule".to_string(), self.schedule.name().to_string()),
+        ];
+        metadata.extend(
+            self.schedule
+                .options()
+                .into_iter()
+                .map(|(name, value)| (format!("schedule_{}", name), value)),
+        );
+        metadata.extend([
+            ("k_neighbour".to_string(), self.k_neighbour.to_string()),
+            ("sticking_probabilities".to_string(), self.sticking_label()),
+            ("seeds".to_string(), self.max_seed.to_string()),
+            ("periodic_bc".to_string(), self.boundary_label()),
+            ("init_seed".to_string(), self.init_seed.to_string()),
+            (
+                "desorption_fraction".to_string(),
+                self.desorption_fraction.to_string(),
+            ),
+            (
+                "desorption_bond_factor".to_string(),
+                self.desorption_bond_factor.to_string(),
+            ),
+            ("flux_profile".to_string(), self.flux.name().to_string()),
+            (
+                "initial_profile".to_string(),
+                self.initial_profile.name().to_string(),
+            ),
+        ]);
+        metadata.extend(
+            self.initial_profile
+                .options()
+                .into_iter()
+                .map(|(name, value)| (format!("initial_{}", name), value)),
+        );
+        metadata.push((
+            "stop_at_saturation".to_string(),
+            self.stop_at_saturation.to_string(),
+        ));
+        if self.stop_at_saturation {
+            metadata.extend([
+                (
+                    "saturation_tolerance".to_string(),
+                    self.saturation_tolerance.to_string(),
+                ),
+                (
+                    "saturation_decades".to_string(),
+                    self.saturation_decades.to_string(),
+                ),
+            ]);
+        }
+        metadata.push((
+            "height_correlation".to_string(),
+            self.height_correlation.to_string(),
+        ));
+        if self.height_correlation {
+            metadata.extend([
+                (
+                    "correlation_every".to_string(),
+                    self.correlation_every.to_string(),
+                ),
+                (
+                    "correlation_fraction".to_string(),
+                    self.correlation_fraction.to_string(),
+                ),
+            ]);
+        }
+        metadata.push((
+            "structure_factor".to_string(),
+            self.structure_factor.to_string(),
+        ));
+        if self.structure_factor {
+            metadata.push((
+                "structure_every".to_string(),
+                self.structure_every.to_string(),
+            ));
+        }
+        metadata.push((
+            "height_histogram".to_string(),
+            self.height_histogram.to_string(),
+        ));
+        if self.height_histogram {
+            metadata.extend([
+                (
+                    "histogram_times".to_string(),
+                    self.histogram_times
+                        .iter()
+                        .map(|t| t.to_string())
+                        .collect::<Vec<String>>()
+                        .join(":"),
+                ),
+                (
+                    "histogram_beta".to_string(),
+                    self.histogram_beta.to_string(),
+                ),
+                (
+                    "histogram_bins".to_string(),
+                    self.histogram_bins.to_string(),
+                ),
+                (
+                    "histogram_range".to_string(),
+                    self.histogram_range.to_string(),
+                ),
+            ]);
+        }
+        metadata.extend([
+            (
+                "standard_errors".to_string(),
+                self.standard_errors.to_string(),
+            ),
+            (
+                "slope_errors".to_string(),
+                self.slope_errors.name().to_string(),
+            ),
+        ]);
+        if self.slope_errors != SlopeErrors::None {
+            metadata.push(("slope_decades".to_string(), self.slope_decades.to_string()));
+            metadata.extend(
+                self.slope_errors
+                    .options()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value)),
+            );
+        }
+        metadata.push(("disorder".to_string(), self.disorder.name().to_string()));
+        metadata.extend(
+            self.disorder
+                .options()
+                .into_iter()
+                .map(|(name, value)| (format!("disorder_{}", name), value)),
+        );
+        metadata.push(("columns".to_string(), self.column_names().join(":")));
+        metadata
+    }
+
+    // Names of the columns of the main .csv, in order, which change with the model and options
+    pub fn column_names(&self) -> Vec<String> {
+        let mut extras: Vec<String> = self
+            .model
+            .observable_names()
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        if self.model == ModelKind::Mixture {
+            for (model, species) in self.mixture_models.iter().zip(["a", "b"]) {
+                extras.extend(
+                    model
+                        .observable_names()
+                        .iter()
+                        .map(|name| format!("{}_{}", species, name)),
+                );
+            }
+        }
+        if self.desorption_fraction > 0.0 {
+            extras.extend(["growth_rate".to_string(), "desorbed".to_string()]);
+        }
+        if self.track_bulk {
+            extras.extend(["density".to_string(), "porosity".to_string()]);
+        }
+
+        let mut names: Vec<String> = vec!["v".to_string(), "h".to_string(), "t".to_string()];
+        names.extend(extras.iter().cloned());
+        names.extend(["skewness".to_string(), "kurtosis".to_string()]);
+        if self.standard_errors {
+            names.extend(
+                ["v", "h"]
+                    .iter()
+                    .map(|name| name.to_string())
+                    .chain(extras)
+                    .map(|name| format!("{}_err", name)),
+            );
+        }
+        if self.slope_errors != SlopeErrors::None {
+            names.extend(["slope".to_string(), "slope_err".to_string()]);
+        }
+        names
+    }
+
+    // Copy of the parameters with this seed's realisation of the quenched disorder drawn
+    fn with_disorder(&self, rng: &mut Ran2Generator) -> SimulationParams {
+        let mut params: SimulationParams = self.clone();
+        params.disorder.realise(self.sites(), rng);
+        params
+    }
+
+    // Label for the model in the output filenames, including any options specific to that model
+    pub fn model_label(&self) -> String {
+        match self.model {
+            ModelKind::Rsos => format!(
+                "{}_N{}_{}",
+                self.model.name(),
+                self.rsos_max_step,
+                if self.rsos_time_per_attempt {
+                    "attempts"
+                } else {
+                    "accepted"
+                }
+            ),
+            ModelKind::Oblique => format!(
+                "{}_theta{}_spread{}",
+                self.model.name(),
+                self.oblique_angle,
+                self.oblique_spread
+            ),
+            ModelKind::Polyomino => format!("{}_{}", self.model.name(), self.polyomino_label),
+            ModelKind::Mixture => format!(
+                "{}_{}-k{}_{}-k{}_p{}",
+                self.model.name(),
+                self.mixture_models[0].name(),
+                self.mixture_k_neighbours[0],
+                self.mixture_models[1].name(),
+                self.mixture_k_neighbours[1],
+                self.mixture_p
+            ),
+            ModelKind::Dla => format!(
+                "{}_launch{}_kill{}",
+                self.model.name(),
+                self.dla_launch_margin,
+                self.dla_kill_radius
+            ),
+            ModelKind::WolfVillain | ModelKind::DasSarma => {
+                format!("{}_l{}", self.model.name(), self.diffusion_length)
+            }
+            _ => String::from(self.model.name()),
+        }
+    }
+}
+
+#[cfg(test)]
+impl SimulationParams {
+    // Parameters for `model` on a flat 1D substrate of length `l` with periodic boundaries, with
+    //  every optional measurement and feature turned off, for the unit tests
+    pub fn for_tests(model: ModelKind, l: i32) -> SimulationParams {
+        SimulationParams {
+            model,
+            length_t_max: (l, 1.0),
+            k_neighbour: 1,
+            sticking_probs: vec![1.0],
+            max_seed: 1,
+            schedule: TimeSchedule::Legacy,
+            dimension: 1,
+            lattice: LatticeKind::Square,
+            periodic_bc: true,
+            periodic_bc_y: true,
+            init_seed: -1,
+            track_bulk: false,
+            stop_at_saturation: false,
+            saturation_tolerance: 0.05,
+            saturation_decades: 0.0,
+            height_correlation: false,
+            correlation_every: 1,
+            correlation_fraction: 1.0,
+            structure_factor: false,
+            structure_every: 1,
+            height_histogram: false,
+            histogram_times: Vec::new(),
+            histogram_beta: 0.0,
+            histogram_bins: 1,
+            histogram_range: 1.0,
+            standard_errors: false,
+            slope_errors: SlopeErrors::None,
+            slope_decades: 1.0,
+            desorption_fraction: 0.0,
+            desorption_bond_factor: 0.0,
+            flux: FluxProfile::Uniform,
+            initial_profile: InitialProfile::Flat,
+            disorder: Disorder::none(),
+            rsos_max_step: 1,
+            rsos_time_per_attempt: true,
+            oblique_angle: 0.0,
+            oblique_spread: 0.0,
+            polyomino_shapes: Vec::new(),
+            polyomino_label: String::new(),
+            mixture_models: [ModelKind::Ballistic; 2],
+            mixture_k_neighbours: [1, 0],
+            mixture_p: 0.5,
+            dla_launch_margin: 1,
+            dla_kill_radius: 2,
+            diffusion_length: 0,
+        }
     }
 }
 
@@ -53,17 +517,229 @@ pub struct SimulationResults {
     avg_v_out: Vec<f64>,
     avg_h_out: Vec<f64>,
     t_out: Vec<f64>,
+    avg_extra_out: Vec<Vec<f64>>,
+    skewness: Vec<f64>, // of the height distribution, from the ensemble averaged central moments
+    kurtosis: Vec<f64>, // excess kurtosis, likewise
+    avg_voids: BTreeMap<usize, f64>,
+    saturation: Option<(f64, f64)>, // crossover time and saturated width, if saturation was found
+    correlations: Vec<(f64, Option<f64>, Vec<f64>)>, // t, xi and G(r) at each time it was measured
+    structure: Vec<(f64, Vec<f64>)>, // t and S(k) at each time it was measured
+    histograms: Vec<(f64, Histogram)>, // t and the rescaled heights of every seed at each time
+    std_errors: Vec<Vec<f64>>, // standard errors of v, h and any extra observables at each time
+    slopes: Vec<(Option<f64>, Option<f64>)>, // local log-slope of v, and its error, at each time
 }
 
 impl SimulationResults {
-    pub fn new(v_out: Vec<f64>, h_out: Vec<f64>, t_out: Vec<f64>) -> SimulationResults {
-        let results = SimulationResults {
+    pub fn new(
+        v_out: Vec<f64>,
+        h_out: Vec<f64>,
+        t_out: Vec<f64>,
+        extra_out: Vec<Vec<f64>>,
+        voids: BTreeMap<usize, f64>,
+    ) -> SimulationResults {
+        SimulationResults {
             avg_v_out: v_out,
             avg_h_out: h_out,
-            t_out: t_out,
-        };
+            t_out,
+            avg_extra_out: extra_out,
+            skewness: Vec::new(),
+            kurtosis: Vec::new(),
+            avg_voids: voids,
+            saturation: None,
+            correlations: Vec::new(),
+            structure: Vec::new(),
+            histograms: Vec::new(),
+            std_errors: Vec::new(),
+            slopes: Vec::new(),
+        }
+    }
+}
+
+// Measurements made for a single seed: time series of the width, mean height, time, any extra
+//  observables and the central moments of the heights, the height-height correlation function and
+//  structure factor at every n-th time point, histograms of the rescaled heights at the selected
+//  times, plus the number of voids of each size left in the bulk at the end of the run
+pub struct SeedData {
+    v_out: Vec<f64>,
+    h_out: Vec<f64>,
+    t_out: Vec<f64>,
+    extra_out: Vec<Vec<f64>>,
+    moments_out: Vec<[f64; 3]>,
+    g_out: Vec<Vec<f64>>,
+    sk_out: Vec<Vec<f64>>,
+    histograms: Vec<(f64, Histogram)>,
+    voids: BTreeMap<usize, u64>,
+}
+
+// State of the simulation for a single seed, which is advanced one time point at a time, so that the
+//  seeds can either run straight through independently or in step with the rest of the ensemble
+struct SeedRun<M: GrowthModel> {
+    params: SimulationParams, // parameters with this seed's quenched disorder drawn
+    rng: Ran2Generator,
+    model: M,
+    desorption: Option<Desorption>,
+    lattice: Lattice,
+    s: Surface,
+    h: f64,           // average height
+    deposited: usize, // number of particles dropped so far, including desorption attempts
+    data: SeedData,
+}
+
+impl<M: GrowthModel> SeedRun<M> {
+    fn new(params: &SimulationParams, seed: i32, t_points: usize) -> SeedRun<M> {
+        // Unpack struct of params
+        let (l, _t_max) = params.length_t_max;
+        let init_seed = params.init_seed;
+
+        // Initialise random seed, based on the current system
+        let idum: i32 = -(l + init_seed + seed).abs();
+
+        // Instantiate a new random number generator with given initial seed
+        let mut rng: Ran2Generator = Ran2Generator::new(idum);
+
+        // Draw the quenched disorder for this seed before anything else uses the generator
+        let params: SimulationParams = params.with_disorder(&mut rng);
+
+        // Instantiate the growth model for this seed, and desorption if particles can desorb
+        let model: M = M::new(&params);
+        let desorption: Option<Desorption> = Desorption::new(&params);
+
+        // Declare surface array, starting from the initial profile. Desorption needs the bulk to
+        //  know where columns fall to
+        let heights: Vec<usize> = params
+            .initial_profile
+            .heights(l, params.dimension, &mut rng);
+        let s: Surface = Surface::new(heights, params.track_bulk || desorption.is_some());
+        let h: f64 = mean(&s[..], s.len()).unwrap();
+        let lattice: Lattice = Lattice::new(&params, 1);
+
+        SeedRun {
+            params,
+            rng,
+            model,
+            desorption,
+            lattice,
+            s,
+            h,
+            deposited: 0,
+            data: SeedData {
+                v_out: Vec::with_capacity(t_points),
+                h_out: Vec::with_capacity(t_points),
+                t_out: Vec::with_capacity(t_points),
+                extra_out: Vec::with_capacity(t_points),
+                moments_out: Vec::with_capacity(t_points),
+                g_out: Vec::new(),
+                sk_out: Vec::new(),
+                histograms: Vec::new(),
+                voids: BTreeMap::new(),
+            },
+        }
+    }
+
+    // Advance to the next time point in the schedule, once `deposits` particles have been dropped in
+    //  total, and measure the surface there
+    fn step(&mut self, deposits: usize) {
+        // Define separate variable for the number of columns on the surface as usize
+        let s_len: usize = self.params.sites();
+
+        // Deposit particles on surface s up to the next time point, or attempt to desorb some of them
+        for _ in self.deposited..deposits {
+            let desorbed: bool = match &mut self.desorption {
+                Some(desorption) => desorption.attempt(&mut self.s, &mut self.rng),
+                None => false,
+            };
+            if !desorbed {
+                self.model.deposit(&mut self.s, &mut self.rng);
+            }
+        }
+        let (h_prev, t_prev) = (self.h, self.t());
+        self.deposited = deposits;
+        self.h = mean(&self.s[..], s_len).unwrap();
+        let moments: [f64; 3] = central_moments(&self.s[..], s_len).unwrap();
+        let v: f64 = moments[0].sqrt(); // interface width
+
+        // Save calculated values for this time point to the vectors
+        let mut extra: Vec<f64> = self.model.observables(&self.s);
+        if let Some(desorption) = &self.desorption {
+            // Net growth rate since the last measurement, and particles desorbed per site
+            extra.extend([
+                (self.h - h_prev) / (self.t() - t_prev),
+                desorption.desorbed() as f64 / s_len as f64,
+            ]);
+        }
+        if self.params.track_bulk {
+            // Bulk density and porosity of the film
+            extra.extend([self.s.density(), 1.0 - self.s.density()]);
+        }
+        if self.params.height_correlation
+            && self
+                .data
+                .t_out
+                .len()
+                .is_multiple_of(self.params.correlation_every)
+        {
+            let (l, _t_max) = self.params.length_t_max;
+            self.data.g_out.push(height_correlation(
+                &self.s,
+                &self.lattice,
+                self.params.dimension,
+                l as usize / 2,
+            ));
+        }
+        if self.params.structure_factor
+            && self
+                .data
+                .t_out
+                .len()
+                .is_multiple_of(self.params.structure_every)
+        {
+            let (l, _t_max) = self.params.length_t_max;
+            self.data
+                .sk_out
+                .push(structure_factor(&self.s, l as usize, self.params.dimension));
+        }
+        if self.params.height_histogram {
+            // Histogram the surface at the first time point at or after each selected time, once even
+            //  if several selected times fall before the same time point
+            let t: f64 = self.t();
+            if self
+                .params
+                .histogram_times
+                .iter()
+                .any(|&t_selected| t_prev < t_selected && t_selected <= t)
+            {
+                let histogram: Histogram = Histogram::of_surface(
+                    &self.s,
+                    t,
+                    self.params.histogram_beta,
+                    self.params.histogram_range,
+                    self.params.histogram_bins,
+                );
+                self.data.histograms.push((t, histogram));
+            }
+        }
+        self.data.v_out.push(v);
+        self.data.h_out.push(self.h);
+        self.data.t_out.push(self.t());
+        self.data.extra_out.push(extra);
+        self.data.moments_out.push(moments);
+    }
 
-        results
+    // Time so far, as the number of particles dropped per column
+    fn t(&self) -> f64 {
+        self.deposited as f64 / self.params.sites() as f64
+    }
+
+    // Interface width at the latest time point
+    fn v(&self) -> f64 {
+        *self.data.v_out.last().unwrap_or(&0.0)
+    }
+
+    // Measure the voids left in the bulk of the film, and hand back everything measured
+    fn finish(self) -> SeedData {
+        let mut data: SeedData = self.data;
+        data.voids = self.s.void_sizes(&self.lattice);
+        data
     }
 }
 
@@ -89,26 +765,9 @@ fn min<T: Ord>(a: T, b: T) -> T {
     }
 }
 
-// Return the largest of three values
-fn max3<T: Ord>(a: T, b: T, c: T) -> T {
-    if a <= b {
-        if b <= c {
-            c
-        } else {
-            b
-        }
-    } else {
-        if a <= c {
-            c
-        } else {
-            a
-        }
-    }
-}
-
-fn mean<'a, T: 'a>(numbers: &'a [T], length: usize) -> Option<f64>
+fn mean<'a, T>(numbers: &'a [T], length: usize) -> Option<f64>
 where
-    T: ToPrimitive + Sum<&'a T>,
+    T: 'a + ToPrimitive + Sum<&'a T>,
 {
     match length {
         positive if positive > 0 => {
@@ -124,9 +783,9 @@ where
 
 // May delete this later. Will require further testing but my hunch is
 //  that parallelisation is pointless here as the overheads are too great
-fn _mean_par<'a, T: 'a + Send + Sync>(numbers: &'a [T]) -> Option<f64>
+fn _mean_par<'a, T>(numbers: &'a [T]) -> Option<f64>
 where
-    T: ToPrimitive + Sum<&'a T> + Sum,
+    T: 'a + Send + Sync + ToPrimitive + Sum<&'a T> + Sum,
 {
     match numbers.len() {
         positive if positive > 0 => {
@@ -140,178 +799,163 @@ where
     }
 }
 
-fn std_dev<'a, T: 'a>(numbers: &'a [T], length: usize) -> Option<f64>
+// Second, third and fourth central moments of the numbers, which give the width, skewness and
+//  kurtosis of the heights
+fn central_moments<'a, T>(numbers: &'a [T], length: usize) -> Option<[f64; 3]>
 where
-    T: ToPrimitive + Sum<&'a T>,
+    T: 'a + ToPrimitive + Sum<&'a T>,
 {
     match (mean(numbers, length), length) {
         (Some(mean_val), count) if count > 0 => {
-            let dev = numbers
-                .iter()
-                .map(|value| {
-                    let value = T::to_f64(&value).unwrap();
-                    let diff = mean_val - value;
-                    diff * diff
-                })
-                .sum::<f64>()
-                / count as f64;
-            Some(dev.sqrt())
+            let mut moments: [f64; 3] = [0.0; 3];
+            for value in numbers {
+                let value = T::to_f64(value).unwrap();
+                let diff = mean_val - value;
+                let diff2 = diff * diff;
+                moments[0] += diff2;
+                moments[1] -= diff2 * diff; // diff is the mean minus the value, so flip the sign
+                moments[2] += diff2 * diff2;
+            }
+            Some(moments.map(|moment| moment / count as f64))
         }
         _ => None,
     }
 }
 
-fn deposit_blocks(
-    n: usize,
-    l: i32,
-    s: &mut Vec<usize>,
-    rng: &mut Ran2Generator,
-    k_neighbour: i32,
-    periodic_bc: bool,
-) {
-    let mut j: i32; // column number
-
-    // k-neighbour sticking
-    let mut h_max: usize;
-    let mut k: i32;
-
-    for _ in 0..n {
-        loop {
-            // Generate a new random column
-            j = (l as f64 * rng.next()) as i32;
-            if j != l {
-                break;
-            }
-        }
-        h_max = s[j as usize] + 1; // initialize h_max to column j
-        k = 0; // reset counter
-
-        // find maximum height of all the neighbour columns and column j
-        while k <= k_neighbour {
-            if periodic_bc == true {
-                h_max = max3(
-                    s[if j - k < 0 {
-                        (l + ((j - k) % l)) as usize
-                    } else {
-                        (j - k) as usize
-                    }],
-                    h_max,
-                    s[((j + k) % l) as usize],
-                );
-            } else {
-                h_max = max3(
-                    s[max(j - k, 0) as usize],
-                    h_max,
-                    s[min(j + k, l - 1) as usize],
-                );
-            }
-            // increment k
-            k += 1;
-        }
-        // set column j to new height
-        s[j as usize] = h_max;
+// Run the simulation for a single seed all the way through, independently of the rest of the
+//  ensemble, measuring the surface once each total number of particles in `deposits` is dropped
+pub fn do_sim<M: GrowthModel>(
+    params: &SimulationParams,
+    seed: i32,
+    deposits: &[usize],
+) -> SeedData {
+    let mut seed_run: SeedRun<M> = SeedRun::new(params, seed, deposits.len());
+    for &n in deposits {
+        seed_run.step(n);
     }
+    seed_run.finish()
 }
 
-pub fn do_sim(
+// Run every seed in step with the rest of the ensemble, watching the ensemble averaged width for a
+//  plateau. Once it's found, only run for `saturation_decades` more decades of time. Returns the data
+//  for each seed, and the crossover time to saturation if it was found
+fn run_to_saturation<M: GrowthModel>(
     params: &SimulationParams,
-    seed: i32,
-    t_points: usize,
-) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
-    // Unpack struct of params
-    let (l, t_max) = params.length_t_max;
-    let k_neighbour = params.k_neighbour;
-    let _ = params.max_seed;
-    let periodic_bc = params.periodic_bc;
-    let init_seed = params.init_seed;
-
-    let mut h: f64; // average height
-    let mut v: f64; // interface width
-
-    // Initialise random seed, based on the current system
-    let idum: i32 = -1 * (l + init_seed + seed).abs();
-
-    // Instantiate a new random number generator with given initial seed
-    let mut rng: Ran2Generator = Ran2Generator::new(idum);
+    deposits: &[usize],
+) -> (Vec<SeedData>, Option<f64>) {
+    let mut seed_runs: Vec<SeedRun<M>> = (0..params.max_seed)
+        .into_par_iter()
+        .map(|seed| SeedRun::new(params, seed, deposits.len()))
+        .collect();
 
-    // Define separate variable for the length of surface as usize
-    let s_len: usize = l as usize;
+    let mut t_out: Vec<f64> = Vec::with_capacity(deposits.len());
+    let mut v_avg: Vec<f64> = Vec::with_capacity(deposits.len());
+    let mut saturation: Option<(f64, f64)> = None; // crossover time, and time it was detected at
 
-    // Declare surface array
-    let mut s: Vec<usize> = vec![0; s_len];
+    for &n in deposits {
+        seed_runs
+            .par_iter_mut()
+            .for_each(|seed_run| seed_run.step(n));
+        let t: f64 = seed_runs[0].t();
+        let v: f64 = seed_runs.iter().map(|seed_run| seed_run.v()).sum::<f64>();
+        t_out.push(t);
+        v_avg.push(v / params.max_seed as f64);
 
-    // Define some counting variables
-    let mut i: usize = 0; // Counter for ensemble data array
-    let mut n: usize; // Number of particles to be dropped next
-    let mut t: f64 = 0.0;
+        match saturation {
+            None => {
+                saturation =
+                    plateau(&t_out, &v_avg, params.saturation_tolerance).map(|t_x| (t_x, t));
+            }
+            Some((_t_x, t_detected)) => {
+                if t >= t_detected * 10f64.powf(params.saturation_decades) {
+                    break;
+                }
+            }
+        }
+    }
 
-    // Define vectors to store caluclated values
-    let mut v_out: Vec<f64> = vec![0.0; t_points];
-    let mut h_out: Vec<f64> = vec![0.0; t_points];
-    let mut t_out: Vec<f64> = vec![0.0; t_points];
+    let data: Vec<SeedData> = seed_runs
+        .into_par_iter()
+        .map(|seed_run| seed_run.finish())
+        .collect();
+    (data, saturation.map(|(t_x, _t_detected)| t_x))
+}
 
-    // For each time-point in our logarithmic timescale
-    while t < t_max as f64 {
-        n = (t * l as f64 / 100.0 + 1.0) as usize; // Number of particles to drop next
-        if n == 1 {
-            n = l as usize
-        }
-        // Deposit n particles on surface s
-        deposit_blocks(n, l, &mut s, &mut rng, k_neighbour, periodic_bc);
-        h = mean(&s, s_len).unwrap();
-        v = std_dev(&s, s_len).unwrap();
-        t += n as f64 / l as f64;
+// Check whether the width has reached a plateau, i.e. whether the size of its effective growth
+//  exponent d ln(w) / d ln(t), fitted by least squares over the last decade of time, has fallen
+//  below `tolerance`. If so, return the start of that decade, which is taken as the crossover time
+fn plateau(t: &[f64], v: &[f64], tolerance: f64) -> Option<f64> {
+    let last: usize = t.len() - 1;
+    // Latest time point at least a decade before the current one
+    let k: usize = t.iter().rposition(|&t_k| t_k <= t[last] / 10.0)?;
+    let slope: f64 = log_slope(&t[k..], &v[k..])?;
 
-        // Save calculated values for this time point to the vectors
-        v_out[i] = v;
-        h_out[i] = h;
-        t_out[i] = t;
+    if slope.abs() < tolerance {
+        Some(t[k])
+    } else {
+        None
+    }
+}
 
-        i += 1;
+// Run the simulation for whichever growth model was selected in `config_sim.ini`
+pub fn run_model(params: SimulationParams) -> Result<(), Box<dyn Error>> {
+    match params.model {
+        ModelKind::Ballistic => run::<Ballistic>(params),
+        ModelKind::RandomDeposition => run::<RandomDeposition>(params),
+        ModelKind::Family => run::<Family>(params),
+        ModelKind::Rsos => run::<Rsos>(params),
+        ModelKind::Oblique => run::<Oblique>(params),
+        ModelKind::Polyomino => run::<Polyomino>(params),
+        ModelKind::Mixture => run::<Mixture>(params),
+        ModelKind::Eden => run::<Eden>(params),
+        ModelKind::Dla => run::<Dla>(params),
+        ModelKind::WolfVillain => run::<WolfVillain>(params),
+        ModelKind::DasSarma => run::<DasSarma>(params),
     }
-    (v_out, h_out, t_out)
 }
 
-pub fn run(params: SimulationParams) -> Result<(), Box<dyn Error>> {
+pub fn run<M: GrowthModel>(params: SimulationParams) -> Result<(), Box<dyn Error>> {
     // Unpack struct of params
-    let (l, t_max) = params.length_t_max;
+    let (_l, t_max) = params.length_t_max;
+    let sites: usize = params.sites();
     let k_neighbour = params.k_neighbour;
     let max_seed = params.max_seed;
-    let periodic_bc = params.periodic_bc;
     let init_seed = params.init_seed;
 
     println!(
         r"Running simulation for
-        Substrate Length = {}, Nearest Neighbours = {}, Max Seed = {},
-        Periodic Boundary Conditions = {}, Initial Seed = {}...",
-        l, k_neighbour, max_seed, periodic_bc, init_seed
+        Model = {}, Substrate = {}, t_max = {}, Nearest Neighbours = {},
+        Sticking Probabilities = {:?}, Max Seed = {}, Periodic Boundary Conditions = {},
+        Initial Seed = {}...",
+        params.model_label(),
+        params.substrate_label(),
+        t_max,
+        k_neighbour,
+        params.sticking_probs,
+        max_seed,
+        params.boundary_label(),
+        init_seed
     );
 
-    // Set current time and counter of total time points
-    let mut t: f64 = 0.0;
-    let mut t_points: usize = 0;
-    while t < t_max as f64 {
-        // Perform a logarithmic timestep
-        let mut n: u32 = (t * l as f64 / 100.0 + 1.0) as u32;
-        if n == 1 {
-            n = l as u32
-        }
-        t += n as f64 / l as f64;
-        // println!("{}", t); // turn on for debugging as necessary
-        t_points += 1;
-    }
+    // Total number of particles to drop by each time point in the schedule
+    let deposits: Vec<usize> = params.schedule.deposits(sites, t_max);
 
     // Generate vector through which to iterate seeds
     let seeds: Vec<i32> = (0..max_seed).collect();
 
-    // Iterate through seeds in parallel and run the ballistic deposition simulation
-    let data: Vec<(Vec<f64>, Vec<f64>, Vec<f64>)> = seeds
-        .par_iter()
-        .map(|seed| {
-            let (v, h, t) = do_sim(&params, *seed, t_points);
-            (v, h, t)
-        })
-        .collect();
+    // Iterate through seeds in parallel and run the ballistic deposition simulation. Watching for
+    //  saturation needs the whole ensemble at each time point, so the seeds then run in step
+    let (data, t_saturation): (Vec<SeedData>, Option<f64>) = if params.stop_at_saturation {
+        run_to_saturation::<M>(&params, &deposits)
+    } else {
+        let data: Vec<SeedData> = seeds
+            .par_iter()
+            .map(|seed| do_sim::<M>(&params, *seed, &deposits))
+            .collect();
+        (data, None)
+    };
+    // Stopping at saturation may have left some time points in the schedule unused
+    let t_points: usize = data[0].t_out.len();
 
     // Done depositing. Now calculate ensemble averages and save to file
 
@@ -319,22 +963,174 @@ pub fn run(params: SimulationParams) -> Result<(), Box<dyn Error>> {
     let mut v_avg: Vec<f64> = vec![0.0; t_points];
     let mut h_avg: Vec<f64> = vec![0.0; t_points];
     let mut t_avg: Vec<f64> = vec![0.0; t_points];
+    let mut extra_avg: Vec<Vec<f64>> = vec![vec![0.0; data[0].extra_out[0].len()]; t_points];
+    let mut voids_avg: BTreeMap<usize, f64> = BTreeMap::new();
+
+    // Average v, h and any extra observables across the ensemble
+    for (i, j) in iproduct!(0..max_seed as usize, 0..t_points) {
+        v_avg[j] += data[i].v_out[j] / max_seed as f64;
+        h_avg[j] += data[i].h_out[j] / max_seed as f64;
+        for (k, x) in data[i].extra_out[j].iter().enumerate() {
+            extra_avg[j][k] += x / max_seed as f64;
+        }
+    }
 
-    // Average v and h across the ensemble
+    // Average the central moments of the heights across the ensemble before taking their ratios, so
+    //  that the skewness and kurtosis aren't biased by noisy seeds
+    let mut moments_avg: Vec<[f64; 3]> = vec![[0.0; 3]; t_points];
     for (i, j) in iproduct!(0..max_seed as usize, 0..t_points) {
-        v_avg[j] += &data[i].0[j] / max_seed as f64;
-        h_avg[j] += &data[i].1[j] / max_seed as f64;
+        for (k, x) in data[i].moments_out[j].iter().enumerate() {
+            moments_avg[j][k] += x / max_seed as f64;
+        }
     }
+    let (skewness, kurtosis): (Vec<f64>, Vec<f64>) = moments_avg
+        .iter()
+        .map(|&[m2, m3, m4]| {
+            if m2 > 0.0 {
+                (m3 / m2.powf(1.5), m4 / (m2 * m2) - 3.0)
+            } else {
+                (0.0, 0.0)
+            }
+        })
+        .unzip();
+
+    // Average number of voids of each size per seed
+    for seed_data in &data {
+        for (&size, &count) in &seed_data.voids {
+            *voids_avg.entry(size).or_insert(0.0) += count as f64 / max_seed as f64;
+        }
+    }
+
+    // Average the height-height correlation function at each time it was measured, and find the
+    //  correlation length from it and the average of w^2
+    let mut correlations: Vec<(f64, Option<f64>, Vec<f64>)> = Vec::new();
+    for (k, j) in (0..t_points)
+        .step_by(params.correlation_every)
+        .take(data[0].g_out.len())
+        .enumerate()
+    {
+        let mut g_avg: Vec<f64> = vec![0.0; data[0].g_out[k].len()];
+        let mut w2_avg: f64 = 0.0;
+        for seed_data in &data {
+            for (g_r, x) in g_avg.iter_mut().zip(&seed_data.g_out[k]) {
+                *g_r += x / max_seed as f64;
+            }
+            w2_avg += seed_data.v_out[j].powi(2) / max_seed as f64;
+        }
+        let xi: Option<f64> = correlation_length(&g_avg, w2_avg, params.correlation_fraction);
+        correlations.push((data[0].t_out[j], xi, g_avg));
+    }
+
+    // Average the structure factor at each time it was measured
+    let mut structure: Vec<(f64, Vec<f64>)> = Vec::new();
+    for (k, j) in (0..t_points)
+        .step_by(params.structure_every)
+        .take(data[0].sk_out.len())
+        .enumerate()
+    {
+        let mut sk_avg: Vec<f64> = vec![0.0; data[0].sk_out[k].len()];
+        for seed_data in &data {
+            for (s_k, x) in sk_avg.iter_mut().zip(&seed_data.sk_out[k]) {
+                *s_k += x / max_seed as f64;
+            }
+        }
+        structure.push((data[0].t_out[j], sk_avg));
+    }
+
+    // Merge the histograms of every seed in parallel. Every seed makes its histograms at the same
+    //  times, so they can be merged pairwise in order
+    let histograms: Vec<(f64, Histogram)> = data
+        .par_iter()
+        .map(|seed_data| seed_data.histograms.clone())
+        .reduce_with(|a, b| {
+            a.into_iter()
+                .zip(b)
+                .map(|((t, mut histogram), (_t, other))| {
+                    histogram.merge(&other);
+                    (t, histogram)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
 
     // Don't need to average time, so can do it in its own loop
-    for j in 0..t_points {
-        t_avg[j] += &data[0].2[j];
+    for (j, t_j) in t_avg.iter_mut().enumerate() {
+        *t_j += data[0].t_out[j];
     }
 
-    let results = SimulationResults::new(v_avg, h_avg, t_avg);
+    // Standard errors of v, h and any extra observables, from their spread across the ensemble
+    let mut std_errors: Vec<Vec<f64>> = Vec::new();
+    if params.standard_errors {
+        for j in 0..t_points {
+            let mut errors: Vec<f64> = vec![
+                standard_error(&data.iter().map(|d| d.v_out[j]).collect::<Vec<f64>>()),
+                standard_error(&data.iter().map(|d| d.h_out[j]).collect::<Vec<f64>>()),
+            ];
+            errors.extend((0..data[0].extra_out[j].len()).map(|k| {
+                standard_error(&data.iter().map(|d| d.extra_out[j][k]).collect::<Vec<f64>>())
+            }));
+            std_errors.push(errors);
+        }
+    }
+
+    // Local log-slopes of the width, with errors from resampling the seeds. Bootstrap samples are
+    //  drawn with the seed after the last of the ensemble, so that they're reproducible
+    let mut slopes: Vec<(Option<f64>, Option<f64>)> = Vec::new();
+    if params.slope_errors != SlopeErrors::None {
+        let (l, _t_max) = params.length_t_max;
+        let mut rng: Ran2Generator = Ran2Generator::new(-(l + init_seed + max_seed).abs());
+        let v_seeds: Vec<&[f64]> = data.iter().map(|d| &d.v_out[..]).collect();
+        slopes = slope_errors(
+            &t_avg,
+            &v_seeds,
+            params.slope_decades,
+            params.slope_errors,
+            &mut rng,
+        );
+    }
+
+    // Saturated width, averaged over every time point from the crossover time onwards
+    let saturation: Option<(f64, f64)> = t_saturation.map(|t_x| {
+        let saturated: Vec<f64> = t_avg
+            .iter()
+            .zip(v_avg.iter())
+            .filter(|(&t_j, _)| t_j >= t_x)
+            .map(|(_, &v_j)| v_j)
+            .collect();
+        (t_x, saturated.iter().sum::<f64>() / saturated.len() as f64)
+    });
+    if params.stop_at_saturation {
+        match saturation {
+            Some((t_x, w_sat)) => println!("Saturated at t_x = {}, w_sat = {}", t_x, w_sat),
+            None => println!("No saturation detected before t_max = {}", t_max),
+        }
+    }
+
+    let mut results = SimulationResults::new(v_avg, h_avg, t_avg, extra_avg, voids_avg);
+    results.skewness = skewness;
+    results.kurtosis = kurtosis;
+    results.saturation = saturation;
+    results.correlations = correlations;
+    results.structure = structure;
+    results.histograms = histograms;
+    results.std_errors = std_errors;
+    results.slopes = slopes;
 
     // Now need to write these results to a csv file
     write_csv(&params, &results, t_points)?;
+    write_metadata(&params, &results)?;
+    if params.track_bulk {
+        write_voids_csv(&params, &results)?;
+    }
+    if params.height_correlation {
+        write_correlation_csv(&params, &results)?;
+    }
+    if params.structure_factor {
+        write_structure_csv(&params, &results)?;
+    }
+    if params.height_histogram {
+        write_histogram_csv(&params, &results)?;
+    }
 
     Ok(())
 }
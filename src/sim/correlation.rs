@@ -0,0 +1,57 @@
+/*
+Library file for the height-height correlation function of the surface.
+
+The height-difference correlation function G(r, t) = <(h(x + r) - h(x))^2> measures how different the
+heights of two columns r sites apart are. Below the correlation length xi(t) it grows as r^(2 alpha),
+which gives the roughness exponent alpha from a single substrate length, and beyond it the columns
+are uncorrelated, so G levels off at 2 w^2. On a 2D substrate, G averages over pairs of columns
+along both axes of the lattice. With open boundaries, only pairs of columns which are both on the
+substrate are counted.
+*/
+
+use super::lattice::Lattice;
+
+/*
+Functions
+*/
+
+// Height-difference correlation function G(r) for each distance r from 1 to `r_max`
+pub fn height_correlation(
+    s: &[usize],
+    lattice: &Lattice,
+    dimension: usize,
+    r_max: usize,
+) -> Vec<f64> {
+    (1..=r_max as i32)
+        .map(|r| {
+            let (sum, pairs): (f64, usize) = [(r, 0), (0, r)][..dimension]
+                .iter()
+                .flat_map(|&offset| {
+                    (0..s.len()).filter_map(move |c| {
+                        lattice
+                            .column(c, offset)
+                            .map(|n| (s[n] as f64 - s[c] as f64).powi(2))
+                    })
+                })
+                .fold((0.0, 0), |(sum, pairs), d2| (sum + d2, pairs + 1));
+            if pairs > 0 {
+                sum / pairs as f64
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+// Correlation length, as the distance at which G(r) first reaches `fraction` of the value 2 w^2 it
+//  levels off at, interpolating linearly between distances. `None` if it never gets there
+pub fn correlation_length(g: &[f64], w2: f64, fraction: f64) -> Option<f64> {
+    let target: f64 = fraction * 2.0 * w2;
+    if target <= 0.0 {
+        return None;
+    }
+    // G(r) is stored from r = 1, so g[i] is G(i + 1), and G(0) = 0
+    let i: usize = g.iter().position(|&g_r| g_r >= target)?;
+    let g_prev: f64 = if i > 0 { g[i - 1] } else { 0.0 };
+    Some(i as f64 + (target - g_prev) / (g[i] - g_prev))
+}
@@ -8,6 +8,7 @@ use std::{env, fs};
 
 use super::{SimulationParams, SimulationResults};
 
+#[allow(clippy::redundant_pattern_matching)]
 pub fn check_folder_exists(folder: &String) -> Result<bool, Box<dyn Error>> {
     // Generate path to folder
     let mut path = env::current_dir()?;
@@ -23,16 +24,20 @@ pub fn check_folder_exists(folder: &String) -> Result<bool, Box<dyn Error>> {
     Ok(folder_exists)
 }
 
-pub fn write_csv(
-    params: &SimulationParams,
-    results: &SimulationResults,
-    t_points: usize
-) -> Result<(), Box<dyn Error>> {
+pub fn gen_filepath(params: &SimulationParams, suffix: &str) -> Result<String, Box<dyn Error>> {
     // Unpack struct of params
-    let (l, _t_max) = params.length_t_max;
+    let model = params.model_label();
+    let substrate = params.substrate_label();
+    let (_l, t_max) = params.length_t_max;
+    let schedule = params.schedule.label();
     let k_neighbour = params.k_neighbour;
+    let p_stick = params.sticking_label();
+    let desorption = params.desorption_label();
+    let flux = params.flux.label();
+    let initial = params.initial_profile.label();
+    let disorder = params.disorder.label();
     let max_seed = params.max_seed;
-    let pbc: i32 = if params.periodic_bc == true {1} else {0};
+    let pbc = params.boundary_label();
     let init_seed = params.init_seed;
     
     // Check if the `data` folder exists. If not, create it
@@ -43,12 +48,119 @@ pub fn write_csv(
 
 
     // Create the filename to write to disk based on the input parameters
-    let filepath = format!("./{}/L{}_k{}_seeds{}_pbc{}_iseed{}.csv", folder, l, k_neighbour, max_seed, pbc, init_seed);
+    let filepath = format!("./{}/{}_{}_tmax{}{}_k{}_p{}{}{}{}{}_seeds{}_pbc{}_iseed{}{}.csv", folder, model, substrate, t_max, schedule, k_neighbour, p_stick, desorption, flux, initial, disorder, max_seed, pbc, init_seed, suffix);
 
-    let mut wtr = csv::Writer::from_path(filepath)?;
+    Ok(filepath)
+}
+
+pub fn write_csv(
+    params: &SimulationParams,
+    results: &SimulationResults,
+    t_points: usize
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "")?)?;
 
     for i in 0..t_points {
-        wtr.write_record(&[&results.avg_v_out[i].to_string()[..], &results.avg_h_out[i].to_string()[..], &results.t_out[i].to_string()[..]])?;
+        // Write one row per time point, with the columns named by `SimulationParams::column_names`
+        let mut record: Vec<String> = vec![results.avg_v_out[i].to_string(), results.avg_h_out[i].to_string(), results.t_out[i].to_string()];
+        record.extend(results.avg_extra_out[i].iter().map(|x| x.to_string()));
+        record.extend([results.skewness[i].to_string(), results.kurtosis[i].to_string()]);
+        if let Some(errors) = results.std_errors.get(i) {
+            record.extend(errors.iter().map(|x| x.to_string()));
+        }
+        if let Some((slope, error)) = results.slopes.get(i) {
+            record.extend([slope, error].iter().map(|x| x.map_or("none".to_string(), |x| x.to_string())));
+        }
+        wtr.write_record(&record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn write_voids_csv(
+    params: &SimulationParams,
+    results: &SimulationResults
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "_voids")?)?;
+
+    for (size, count) in &results.avg_voids {
+        // Write the size of each void, followed by the average number of voids of that size per seed
+        wtr.write_record(&[size.to_string(), count.to_string()])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn write_correlation_csv(
+    params: &SimulationParams,
+    results: &SimulationResults
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "_correlation")?)?;
+
+    for (t, xi, g) in &results.correlations {
+        // Write t and the correlation length, followed by G(r) for each r from 1 to L/2
+        let xi: String = xi.map_or("none".to_string(), |xi| xi.to_string());
+        let mut record: Vec<String> = vec![t.to_string(), xi];
+        record.extend(g.iter().map(|g_r| g_r.to_string()));
+        wtr.write_record(&record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn write_structure_csv(
+    params: &SimulationParams,
+    results: &SimulationResults
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "_structure")?)?;
+
+    for (t, s_k) in &results.structure {
+        // Write t, followed by S(k) for each k from 1 to L/2
+        let mut record: Vec<String> = vec![t.to_string()];
+        record.extend(s_k.iter().map(|s_k| s_k.to_string()));
+        wtr.write_record(&record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn write_histogram_csv(
+    params: &SimulationParams,
+    results: &SimulationResults
+) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "_histogram")?)?;
+
+    for (t, histogram) in &results.histograms {
+        for (x, density) in histogram.density() {
+            // Write t, followed by the centre of each bin and the probability density there
+            wtr.write_record(&[t.to_string(), x.to_string(), density.to_string()])?;
+        }
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+pub fn write_metadata(params: &SimulationParams, results: &SimulationResults) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(gen_filepath(params, "_meta")?)?;
+
+    for (name, value) in params.metadata() {
+        // Write the name of each parameter, followed by its value
+        wtr.write_record(&[name, value])?;
+    }
+
+    // Crossover time and saturated width, when the run was watching for saturation
+    if params.stop_at_saturation {
+        let (t_x, w_sat) = match results.saturation {
+            Some((t_x, w_sat)) => (t_x.to_string(), w_sat.to_string()),
+            None => ("none".to_string(), "none".to_string()),
+        };
+        wtr.write_record(&["saturation_time".to_string(), t_x])?;
+        wtr.write_record(&["saturation_width".to_string(), w_sat])?;
     }
 
     wtr.flush()?;
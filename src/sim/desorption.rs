@@ -0,0 +1,67 @@
+/*
+Library file for desorption (evaporation) of particles from the surface.
+
+Desorption is interleaved with deposition by whichever growth model is being simulated: each event is
+a desorption attempt with probability `desorption_fraction`, and a deposition otherwise. A desorption
+attempt picks a column at random and removes its top particle with probability b^n, where n is the
+number of nearest neighbour columns at least as high as it (i.e. its lateral bonds) and b is
+`desorption_bond_factor`. Only deposited particles desorb, so columns which are back down to the
+initial substrate are left alone. Time counts every event, whether deposition or desorption.
+*/
+
+use super::lattice::Lattice;
+use super::random::Ran2Generator;
+use super::surface::Surface;
+use super::SimulationParams;
+
+pub struct Desorption {
+    fraction: f64,    // probability that each event is a desorption attempt
+    bond_factor: f64, // factor by which each lateral bond reduces the chance of removal
+    lattice: Lattice,
+    desorbed: u64, // total number of particles removed
+}
+
+impl Desorption {
+    // Build the desorption process for `params`, or `None` if particles never desorb
+    pub fn new(params: &SimulationParams) -> Option<Desorption> {
+        if params.desorption_fraction > 0.0 {
+            Some(Desorption {
+                fraction: params.desorption_fraction,
+                bond_factor: params.desorption_bond_factor,
+                lattice: Lattice::new(params, 1),
+                desorbed: 0,
+            })
+        } else {
+            None
+        }
+    }
+
+    // Decide whether the next event is a desorption attempt, and if so carry it out
+    pub fn attempt(&mut self, s: &mut Surface, rng: &mut Ran2Generator) -> bool {
+        if rng.next() >= self.fraction {
+            return false;
+        }
+
+        let c: usize = self.lattice.random_site(rng);
+        let bonds: i32 = self
+            .lattice
+            .shell(1)
+            .iter()
+            .filter(|&&offset| match self.lattice.column(c, offset) {
+                Some(n) => s[n] >= s[c],
+                None => false,
+            })
+            .count() as i32;
+
+        if s.removable(c) && rng.next() < self.bond_factor.powi(bonds) {
+            s.remove(c);
+            self.desorbed += 1;
+        }
+        true
+    }
+
+    // Total number of particles removed so far
+    pub fn desorbed(&self) -> u64 {
+        self.desorbed
+    }
+}
@@ -0,0 +1,210 @@
+/*
+Library file for quenched disorder in the substrate.
+
+Rather than every column behaving the same, each column (or each site in the bulk) can be given a
+random value, which is frozen in place for the whole of a run:
+    - `sticking`: A sticking strength, which multiplies the chance of a particle sticking to the side
+        of that column (or site) as it falls past it in the `ballistic` model
+    - `growth`: A growth rate multiplier, which multiplies the flux of particles onto that column
+
+The values are drawn once per seed, from the same random number generator (and so the same `idum`)
+as the rest of the run, before anything else uses it. The same seed therefore always gives the same
+realisation of the disorder, whichever model is being grown on it. Per-site values are looked up by
+hashing the site with a key drawn for the seed, so they don't depend on the order sites are visited.
+*/
+
+use super::random::Ran2Generator;
+
+/*
+Enums
+*/
+
+// Names of the kinds, scales and distributions of disorder which can be supplied in `config_sim.ini`
+pub const DISORDER_NAMES: [&str; 3] = ["none", "sticking", "growth"];
+pub const DISORDER_SCALE_NAMES: [&str; 2] = ["column", "site"];
+pub const DISTRIBUTION_NAMES: [&str; 2] = ["uniform", "bimodal"];
+
+// What the random values of the disorder multiply
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DisorderKind {
+    None,
+    Sticking,
+    Growth,
+}
+
+// Distribution which the random values are drawn from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Distribution {
+    Uniform { low: f64, high: f64 },
+    Bimodal { low: f64, high: f64, fraction: f64 },
+}
+
+impl Distribution {
+    // Value for a uniform random number `u` from 0 to 1, so that each value takes one draw
+    fn quantile(&self, u: f64) -> f64 {
+        match *self {
+            Distribution::Uniform { low, high } => low + (high - low) * u,
+            Distribution::Bimodal {
+                low,
+                high,
+                fraction,
+            } => {
+                if u < fraction {
+                    high
+                } else {
+                    low
+                }
+            }
+        }
+    }
+
+    // Label for the distribution in the output filenames and run metadata, e.g. `uniform0-1`
+    fn label(&self) -> String {
+        match self {
+            Distribution::Uniform { low, high } => format!("uniform{}-{}", low, high),
+            Distribution::Bimodal {
+                low,
+                high,
+                fraction,
+            } => format!("bimodal{}-{}_f{}", low, high, fraction),
+        }
+    }
+}
+
+/*
+Structs
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Disorder {
+    kind: DisorderKind,
+    per_site: bool, // whether each site in the bulk, rather than each column, has its own value
+    distribution: Distribution,
+    columns: Vec<f64>, // value for each column, once drawn
+    key: u64,          // key for hashing sites, once drawn
+}
+
+impl Disorder {
+    pub fn new(kind: DisorderKind, per_site: bool, distribution: Distribution) -> Disorder {
+        Disorder {
+            kind,
+            per_site,
+            distribution,
+            columns: Vec::new(),
+            key: 0,
+        }
+    }
+
+    // No disorder, so that every column and site behaves the same
+    pub fn none() -> Disorder {
+        Disorder::new(
+            DisorderKind::None,
+            false,
+            Distribution::Uniform {
+                low: 1.0,
+                high: 1.0,
+            },
+        )
+    }
+
+    pub fn kind(&self) -> DisorderKind {
+        self.kind
+    }
+
+    // Draw the realisation of the disorder for a substrate of `sites` columns. Nothing is drawn
+    //  without any disorder
+    pub fn realise(&mut self, sites: usize, rng: &mut Ran2Generator) {
+        if self.kind == DisorderKind::None {
+            return;
+        }
+        if self.per_site {
+            self.key = (rng.next() * (1u64 << 53) as f64) as u64;
+        } else {
+            self.columns = (0..sites)
+                .map(|_| self.distribution.quantile(rng.next()))
+                .collect();
+        }
+    }
+
+    // Sticking strength of the site at height `h` in column `c`
+    pub fn sticking(&self, c: usize, h: usize) -> f64 {
+        match self.kind {
+            DisorderKind::Sticking => self.value(c, h),
+            _ => 1.0,
+        }
+    }
+
+    // Growth rate multiplier for each column, or `None` if the growth rate doesn't vary
+    pub fn growth_rates(&self) -> Option<&[f64]> {
+        match self.kind {
+            DisorderKind::Growth => Some(&self.columns),
+            _ => None,
+        }
+    }
+
+    // Label for the disorder in the output filenames, e.g. `_qsticking_column_uniform0-1`, which is
+    //  left out without any disorder
+    pub fn label(&self) -> String {
+        match self.kind {
+            DisorderKind::None => String::new(),
+            _ => format!(
+                "_q{}_{}_{}",
+                self.name(),
+                self.scale_name(),
+                self.distribution.label()
+            ),
+        }
+    }
+
+    // Options of the disorder, as (name, value) pairs for the run metadata
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        match self.kind {
+            DisorderKind::None => Vec::new(),
+            _ => vec![
+                ("scale", self.scale_name().to_string()),
+                ("distribution", self.distribution.label()),
+            ],
+        }
+    }
+
+    // Name of the kind of disorder, as used in `config_sim.ini`
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            DisorderKind::None => "none",
+            DisorderKind::Sticking => "sticking",
+            DisorderKind::Growth => "growth",
+        }
+    }
+
+    fn scale_name(&self) -> &'static str {
+        if self.per_site {
+            "site"
+        } else {
+            "column"
+        }
+    }
+
+    fn value(&self, c: usize, h: usize) -> f64 {
+        if self.per_site {
+            self.distribution.quantile(hash_uniform(self.key, c, h))
+        } else {
+            self.columns[c]
+        }
+    }
+}
+
+/*
+Functions
+*/
+
+// Uniform random number from 0 to 1 for the site at height `h` in column `c`, by mixing them into
+//  the key with the SplitMix64 finaliser
+fn hash_uniform(key: u64, c: usize, h: usize) -> f64 {
+    let mut z: u64 = key
+        ^ (c as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (h as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,202 @@
+/*
+Library file for the fast Fourier transform, written in pure Rust so that the simulation doesn't depend
+on any system libraries.
+
+Lengths which are a power of two use the iterative radix-2 Cooley-Tukey algorithm. Any other length n
+uses Bluestein's algorithm, which rewrites the transform as a convolution and does that with radix-2
+transforms of length at least 2n - 1, so that every substrate length takes O(n log n) time.
+
+Complex numbers are (re, im) pairs. Transforms are unnormalised in both directions, so an inverse
+transform after a forward one multiplies every value by the length.
+*/
+
+use std::f64::consts::PI;
+
+pub type Complex = (f64, f64);
+
+/*
+Functions
+*/
+
+// Discrete Fourier transform of `x` in place, X_k = sum_j x_j exp(-2 pi i j k / n), or the inverse
+//  transform with exp(+2 pi i j k / n) instead
+pub fn fft(x: &mut [Complex], inverse: bool) {
+    let n: usize = x.len();
+    if n <= 1 {
+        return;
+    }
+    if n.is_power_of_two() {
+        radix2(x, inverse);
+    } else {
+        bluestein(x, inverse);
+    }
+}
+
+// Transform an n x n (or just n, in 1D) array in place, stored with the first axis running fastest
+//  like the columns of the surface, one axis at a time
+pub fn fft_nd(x: &mut [Complex], n: usize, dimension: usize, inverse: bool) {
+    let sites: usize = n.pow(dimension as u32);
+    let mut line: Vec<Complex> = vec![(0.0, 0.0); n];
+    for axis in 0..dimension {
+        let stride: usize = n.pow(axis as u32);
+        for start in (0..sites).filter(|site| (site / stride).is_multiple_of(n)) {
+            for (i, value) in line.iter_mut().enumerate() {
+                *value = x[start + i * stride];
+            }
+            fft(&mut line, inverse);
+            for (i, value) in line.iter().enumerate() {
+                x[start + i * stride] = *value;
+            }
+        }
+    }
+}
+
+// Wavenumber of mode `k` of a transform of length `n`, folded into -n/2..n/2
+pub fn wavenumber(k: usize, n: usize) -> f64 {
+    if k <= n / 2 {
+        k as f64
+    } else {
+        k as f64 - n as f64
+    }
+}
+
+fn mul((a, b): Complex, (c, d): Complex) -> Complex {
+    (a * c - b * d, a * d + b * c)
+}
+
+fn conj((a, b): Complex) -> Complex {
+    (a, -b)
+}
+
+// Iterative Cooley-Tukey transform for lengths which are a power of two
+fn radix2(x: &mut [Complex], inverse: bool) {
+    let n: usize = x.len();
+    let bits: u32 = n.trailing_zeros();
+    let sign: f64 = if inverse { 1.0 } else { -1.0 };
+
+    // Put the values in bit-reversed order, so that each stage combines neighbouring blocks
+    for i in 0..n {
+        let j: usize = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            x.swap(i, j);
+        }
+    }
+
+    // Combine pairs of transforms of length `half` into transforms of twice the length
+    let mut half: usize = 1;
+    while half < n {
+        let twiddles: Vec<Complex> = (0..half)
+            .map(|k| {
+                let angle: f64 = sign * PI * k as f64 / half as f64;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        for start in (0..n).step_by(2 * half) {
+            for (k, &twiddle) in twiddles.iter().enumerate() {
+                let a: Complex = x[start + k];
+                let b: Complex = mul(x[start + k + half], twiddle);
+                x[start + k] = (a.0 + b.0, a.1 + b.1);
+                x[start + k + half] = (a.0 - b.0, a.1 - b.1);
+            }
+        }
+        half *= 2;
+    }
+}
+
+// Bluestein's transform for any length, using 2 j k = j^2 + k^2 - (k - j)^2 to turn the transform
+//  into a convolution with the chirp exp(-pi i j^2 / n)
+fn bluestein(x: &mut [Complex], inverse: bool) {
+    let n: usize = x.len();
+    let m: usize = (2 * n - 1).next_power_of_two();
+    let sign: f64 = if inverse { 1.0 } else { -1.0 };
+
+    // Reduce j^2 modulo 2n before scaling, so the angle stays accurate for long lines
+    let chirp: Vec<Complex> = (0..n)
+        .map(|j| {
+            let angle: f64 = sign * PI * ((j * j) % (2 * n)) as f64 / n as f64;
+            (angle.cos(), angle.sin())
+        })
+        .collect();
+
+    let mut a: Vec<Complex> = vec![(0.0, 0.0); m];
+    for j in 0..n {
+        a[j] = mul(x[j], chirp[j]);
+    }
+    let mut b: Vec<Complex> = vec![(0.0, 0.0); m];
+    b[0] = conj(chirp[0]);
+    for j in 1..n {
+        b[j] = conj(chirp[j]);
+        b[m - j] = conj(chirp[j]);
+    }
+
+    // Convolve by multiplying the transforms, then scale back for the unnormalised inverse
+    radix2(&mut a, false);
+    radix2(&mut b, false);
+    for (a_k, &b_k) in a.iter_mut().zip(&b) {
+        *a_k = mul(*a_k, b_k);
+    }
+    radix2(&mut a, true);
+    for k in 0..n {
+        let (re, im): Complex = mul(a[k], chirp[k]);
+        x[k] = (re / m as f64, im / m as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Direct O(n^2) discrete Fourier transform to check against
+    fn naive_dft(x: &[Complex], inverse: bool) -> Vec<Complex> {
+        let n: usize = x.len();
+        let sign: f64 = if inverse { 1.0 } else { -1.0 };
+        (0..n)
+            .map(|k| {
+                x.iter()
+                    .enumerate()
+                    .fold((0.0, 0.0), |(re, im), (j, &x_j)| {
+                        let angle: f64 = sign * 2.0 * PI * ((j * k) % n) as f64 / n as f64;
+                        let (a, b): Complex = mul(x_j, (angle.cos(), angle.sin()));
+                        (re + a, im + b)
+                    })
+            })
+            .collect()
+    }
+
+    // Forward and inverse transforms of an arbitrary complex signal of length `n`
+    fn check_against_naive(n: usize) {
+        let x: Vec<Complex> = (0..n)
+            .map(|j| {
+                (
+                    (j as f64 * 0.7).sin() + 0.3,
+                    (j as f64 * 1.3).cos() - 0.1 * j as f64,
+                )
+            })
+            .collect();
+        for inverse in [false, true] {
+            let expected: Vec<Complex> = naive_dft(&x, inverse);
+            let mut actual: Vec<Complex> = x.clone();
+            fft(&mut actual, inverse);
+            for (a, e) in actual.iter().zip(&expected) {
+                assert!(
+                    (a.0 - e.0).abs() < 1e-9 && (a.1 - e.1).abs() < 1e-9,
+                    "length {}: got {:?}, expected {:?}",
+                    n,
+                    a,
+                    e
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn radix2_matches_naive_dft() {
+        check_against_naive(64);
+    }
+
+    #[test]
+    fn bluestein_matches_naive_dft() {
+        check_against_naive(100);
+        check_against_naive(37);
+    }
+}
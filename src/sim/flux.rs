@@ -0,0 +1,234 @@
+/*
+Library file for non-uniform flux profiles across the substrate.
+
+By default particles land on every column with equal probability, but the flux can instead follow a
+profile across the substrate:
+    - `gaussian`: A Gaussian beam of standard deviation `width` sites, centred `centre` of the way
+        across the substrate (in both directions on a 2D substrate)
+    - `gradient`: A linear gradient along the first axis, from 1 - `gradient` at one edge to
+        1 + `gradient` at the other
+    - `mask`: A periodic mask of stripes along the first axis, `period` sites apart, where the first
+        `duty` of each period is open and the rest lets through only `mask_level` of the flux
+    - `file`: Weights for each column read from a file, separated by commas or new lines, with rows
+        of the lattice one after another on a 2D substrate
+
+Columns are then drawn from the profile with the alias method, which takes constant time per particle
+however large the substrate is.
+*/
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use super::random::Ran2Generator;
+
+/*
+Enums
+*/
+
+// Names of the flux profiles which can be supplied in `config_sim.ini`
+pub const FLUX_NAMES: [&str; 5] = ["uniform", "gaussian", "gradient", "mask", "file"];
+
+// Relative flux of particles onto each column of the substrate
+#[derive(Debug, Clone, PartialEq)]
+pub enum FluxProfile {
+    Uniform,
+    Gaussian {
+        centre: f64,
+        width: f64,
+    },
+    Gradient {
+        gradient: f64,
+    },
+    Mask {
+        period: usize,
+        duty: f64,
+        level: f64,
+    },
+    File {
+        path: String,
+        weights: Vec<f64>,
+    },
+}
+
+impl FluxProfile {
+    // Read the weights for a `file` profile
+    pub fn from_file(path: &str) -> Result<FluxProfile, Box<dyn Error>> {
+        Ok(FluxProfile::File {
+            path: path.to_string(),
+            weights: read_values(path)?,
+        })
+    }
+
+    // Name of the profile, as used in `config_sim.ini`
+    pub fn name(&self) -> &'static str {
+        match self {
+            FluxProfile::Uniform => "uniform",
+            FluxProfile::Gaussian { .. } => "gaussian",
+            FluxProfile::Gradient { .. } => "gradient",
+            FluxProfile::Mask { .. } => "mask",
+            FluxProfile::File { .. } => "file",
+        }
+    }
+
+    // Label for the profile in the output filenames, which is left out for a uniform flux
+    pub fn label(&self) -> String {
+        match self {
+            FluxProfile::Uniform => String::new(),
+            FluxProfile::Gaussian { centre, width } => {
+                format!("_fluxgaussian_c{}_w{}", centre, width)
+            }
+            FluxProfile::Gradient { gradient } => format!("_fluxgradient_g{}", gradient),
+            FluxProfile::Mask {
+                period,
+                duty,
+                level,
+            } => format!("_fluxmask_P{}_d{}_m{}", period, duty, level),
+            FluxProfile::File { path, .. } => format!(
+                "_fluxfile_{}",
+                Path::new(path)
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or("file")
+            ),
+        }
+    }
+
+    // Relative flux onto each column of an L x L (or just L, in 1D) substrate, or `None` if the flux
+    //  is uniform
+    pub fn weights(&self, l: i32, dimension: usize) -> Option<Vec<f64>> {
+        let sites: usize = (l as usize).pow(dimension as u32);
+        let coords =
+            (0..sites).map(|site| ((site % l as usize) as f64, (site / l as usize) as f64));
+
+        let weights: Vec<f64> = match self {
+            FluxProfile::Uniform => return None,
+            FluxProfile::Gaussian { centre, width } => {
+                let x0: f64 = centre * (l - 1) as f64;
+                let y0: f64 = if dimension == 1 { 0.0 } else { x0 };
+                coords
+                    .map(|(x, y)| {
+                        let r2: f64 = (x - x0).powi(2) + (y - y0).powi(2);
+                        (-r2 / (2.0 * width * width)).exp()
+                    })
+                    .collect()
+            }
+            FluxProfile::Gradient { gradient } => coords
+                .map(|(x, _)| 1.0 - gradient + 2.0 * gradient * x / (l - 1) as f64)
+                .collect(),
+            FluxProfile::Mask {
+                period,
+                duty,
+                level,
+            } => coords
+                .map(|(x, _)| {
+                    if ((x as usize % period) as f64) < duty * *period as f64 {
+                        1.0
+                    } else {
+                        *level
+                    }
+                })
+                .collect(),
+            FluxProfile::File { weights, .. } => weights.clone(),
+        };
+        Some(weights)
+    }
+}
+
+/*
+Structs
+*/
+
+// Table for drawing columns in proportion to their weights in constant time, using Vose's alias method
+pub struct AliasTable {
+    prob: Vec<f64>,    // chance of keeping each column when it's drawn
+    alias: Vec<usize>, // column to take instead when it isn't kept
+}
+
+impl AliasTable {
+    pub fn new(weights: &[f64]) -> AliasTable {
+        let n: usize = weights.len();
+        let total: f64 = weights.iter().sum();
+
+        // Scale the weights so that they average to 1, and split them into those above and below
+        let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..n).partition(|&i| scaled[i] < 1.0);
+
+        let mut prob: Vec<f64> = vec![1.0; n];
+        let mut alias: Vec<usize> = (0..n).collect();
+
+        // Pair each column below average with one above it, which tops it up to the average
+        while let (Some(&s), Some(&g)) = (small.last(), large.last()) {
+            small.pop();
+            prob[s] = scaled[s];
+            alias[s] = g;
+            scaled[g] -= 1.0 - scaled[s];
+            if scaled[g] < 1.0 {
+                large.pop();
+                small.push(g);
+            }
+        }
+        // Anything left over is (up to rounding) exactly average, so is always kept
+
+        AliasTable { prob, alias }
+    }
+
+    // Draw a column in proportion to its weight
+    pub fn sample(&self, rng: &mut Ran2Generator) -> usize {
+        let i: usize = ((self.prob.len() as f64 * rng.next()) as usize).min(self.prob.len() - 1);
+        if rng.next() < self.prob[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
+}
+
+/*
+Functions
+*/
+
+// Read one value per column from a file, separated by commas or new lines
+pub fn read_values(path: &str) -> Result<Vec<f64>, Box<dyn Error>> {
+    let values: Vec<f64> = fs::read_to_string(path)?
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.parse())
+        .collect::<Result<Vec<f64>, _>>()?;
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_table_samples_in_proportion_to_weights() {
+        let weights: [f64; 7] = [1.0, 0.0, 3.0, 0.5, 2.5, 1.0, 0.25];
+        let total: f64 = weights.iter().sum();
+        let table: AliasTable = AliasTable::new(&weights);
+        let mut rng: Ran2Generator = Ran2Generator::new(-1);
+
+        let draws: usize = 1_000_000;
+        let mut counts: Vec<usize> = vec![0; weights.len()];
+        for _ in 0..draws {
+            counts[table.sample(&mut rng)] += 1;
+        }
+
+        // Each frequency should be within a few standard deviations of its expected value
+        for (&w, &count) in weights.iter().zip(&counts) {
+            let p: f64 = w / total;
+            let sigma: f64 = (p * (1.0 - p) / draws as f64).sqrt();
+            let frequency: f64 = count as f64 / draws as f64;
+            assert!(
+                (frequency - p).abs() <= 5.0 * sigma,
+                "weight {} drawn with frequency {}, expected {}",
+                w,
+                frequency,
+                p
+            );
+        }
+        assert_eq!(counts[1], 0);
+    }
+}
@@ -0,0 +1,78 @@
+/*
+Library file for histograms of the rescaled heights of the surface.
+
+At each selected time, the heights of every column are rescaled to x = (h - <h>) / t^beta, where <h> is
+the mean height of that surface, and counted into fixed bins from -`range` to `range`. Fixed bins let
+the histograms of every seed be merged by adding their counts, so the distribution across all columns
+and seeds is built up without keeping any of the surfaces. With beta set to the growth exponent, the
+rescaled distributions at different times collapse onto each other, and can be compared with the
+Tracy-Widom distributions of the KPZ class.
+*/
+
+/*
+Structs
+*/
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    low: f64,         // lower edge of the first bin
+    width: f64,       // width of each bin
+    counts: Vec<u64>, // number of values in each bin
+    outside: u64,     // number of values beyond either end of the bins
+}
+
+impl Histogram {
+    // Empty histogram with `bins` bins from -`range` to `range`
+    pub fn new(range: f64, bins: usize) -> Histogram {
+        Histogram {
+            low: -range,
+            width: 2.0 * range / bins as f64,
+            counts: vec![0; bins],
+            outside: 0,
+        }
+    }
+
+    // Histogram of the heights of surface `s` at time `t`, rescaled by t^beta
+    pub fn of_surface(s: &[usize], t: f64, beta: f64, range: f64, bins: usize) -> Histogram {
+        let mut histogram: Histogram = Histogram::new(range, bins);
+        let mean: f64 = s.iter().sum::<usize>() as f64 / s.len() as f64;
+        let scale: f64 = t.powf(beta);
+        for &h in s {
+            histogram.add((h as f64 - mean) / scale);
+        }
+        histogram
+    }
+
+    pub fn add(&mut self, x: f64) {
+        let bin: f64 = ((x - self.low) / self.width).floor();
+        if bin >= 0.0 && bin < self.counts.len() as f64 {
+            self.counts[bin as usize] += 1;
+        } else {
+            self.outside += 1;
+        }
+    }
+
+    // Add the counts of another histogram with the same bins to this one
+    pub fn merge(&mut self, other: &Histogram) {
+        for (count, other_count) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other_count;
+        }
+        self.outside += other.outside;
+    }
+
+    // Centre of each bin, and the probability density there. The density is normalised by every
+    //  value counted, including those outside the bins, so it only integrates to 1 if none were
+    pub fn density(&self) -> Vec<(f64, f64)> {
+        let total: u64 = self.counts.iter().sum::<u64>() + self.outside;
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(bin, &count)| {
+                (
+                    self.low + (bin as f64 + 0.5) * self.width,
+                    count as f64 / (total.max(1) as f64 * self.width),
+                )
+            })
+            .collect()
+    }
+}
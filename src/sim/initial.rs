@@ -0,0 +1,195 @@
+/*
+Library file for the initial profile of the substrate.
+
+Rather than starting from a flat substrate, runs can start from a pre-patterned interface, to study how
+rough or patterned substrates relax and smooth out as the film grows:
+    - `steps`: A staircase of terraces `period` sites wide, each `amplitude` higher than the last
+    - `sinusoid`: A sinusoid of wavelength `period` sites and amplitude `amplitude`
+    - `groove`: V-grooves `period` sites wide and `amplitude` deep
+    - `rough`: A random self-affine profile with roughness exponent `alpha` and an interface width
+        of `amplitude`, drawn afresh for each seed
+    - `file`: Heights for each column read from a file, separated by commas or new lines, with rows
+        of the lattice one after another on a 2D substrate
+
+Steps, sinusoids and grooves run along the first axis of a 2D substrate. Heights are rounded to
+whole sites, and shifted so that the lowest column starts at height 0.
+*/
+
+use std::error::Error;
+use std::f64::consts::PI;
+use std::path::Path;
+
+use super::fft::{fft_nd, wavenumber, Complex};
+use super::flux::read_values;
+use super::random::Ran2Generator;
+
+/*
+Enums
+*/
+
+// Names of the initial profiles which can be supplied in `config_sim.ini`
+pub const INITIAL_NAMES: [&str; 6] = ["flat", "steps", "sinusoid", "groove", "rough", "file"];
+
+// Heights of the columns of the substrate before anything is deposited
+#[derive(Debug, Clone, PartialEq)]
+pub enum InitialProfile {
+    Flat,
+    Steps { amplitude: f64, period: f64 },
+    Sinusoid { amplitude: f64, period: f64 },
+    Groove { amplitude: f64, period: f64 },
+    Rough { alpha: f64, amplitude: f64 },
+    File { path: String, heights: Vec<f64> },
+}
+
+impl InitialProfile {
+    // Read the heights for a `file` profile
+    pub fn from_file(path: &str) -> Result<InitialProfile, Box<dyn Error>> {
+        Ok(InitialProfile::File {
+            path: path.to_string(),
+            heights: read_values(path)?,
+        })
+    }
+
+    // Name of the profile, as used in `config_sim.ini`
+    pub fn name(&self) -> &'static str {
+        match self {
+            InitialProfile::Flat => "flat",
+            InitialProfile::Steps { .. } => "steps",
+            InitialProfile::Sinusoid { .. } => "sinusoid",
+            InitialProfile::Groove { .. } => "groove",
+            InitialProfile::Rough { .. } => "rough",
+            InitialProfile::File { .. } => "file",
+        }
+    }
+
+    // Label for the profile in the output filenames, which is left out for a flat substrate
+    pub fn label(&self) -> String {
+        match self {
+            InitialProfile::Flat => String::new(),
+            InitialProfile::Steps { amplitude, period }
+            | InitialProfile::Sinusoid { amplitude, period }
+            | InitialProfile::Groove { amplitude, period } => {
+                format!("_init{}_A{}_P{}", self.name(), amplitude, period)
+            }
+            InitialProfile::Rough { alpha, amplitude } => {
+                format!("_initrough_a{}_A{}", alpha, amplitude)
+            }
+            InitialProfile::File { path, .. } => format!("_initfile_{}", file_stem(path)),
+        }
+    }
+
+    // Options of the profile, as (name, value) pairs for the run metadata
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        match self {
+            InitialProfile::Flat => Vec::new(),
+            InitialProfile::Steps { amplitude, period }
+            | InitialProfile::Sinusoid { amplitude, period }
+            | InitialProfile::Groove { amplitude, period } => vec![
+                ("amplitude", amplitude.to_string()),
+                ("period", period.to_string()),
+            ],
+            InitialProfile::Rough { alpha, amplitude } => vec![
+                ("alpha", alpha.to_string()),
+                ("amplitude", amplitude.to_string()),
+            ],
+            InitialProfile::File { path, .. } => vec![("file", path.clone())],
+        }
+    }
+
+    // Heights of each column of an L x L (or just L, in 1D) substrate. Only `rough` profiles draw
+    //  any random numbers
+    pub fn heights(&self, l: i32, dimension: usize, rng: &mut Ran2Generator) -> Vec<usize> {
+        let sites: usize = (l as usize).pow(dimension as u32);
+        let x = (0..sites).map(|site| (site % l as usize) as f64);
+
+        let profile: Vec<f64> = match self {
+            InitialProfile::Flat => return vec![0; sites],
+            InitialProfile::Steps { amplitude, period } => {
+                x.map(|x| amplitude * (x / period).floor()).collect()
+            }
+            InitialProfile::Sinusoid { amplitude, period } => x
+                .map(|x| amplitude * (2.0 * PI * x / period).sin())
+                .collect(),
+            InitialProfile::Groove { amplitude, period } => x
+                .map(|x| amplitude * (2.0 * (x / period).fract() - 1.0).abs())
+                .collect(),
+            InitialProfile::Rough { alpha, amplitude } => {
+                rough_profile(l as usize, dimension, *alpha, *amplitude, rng)
+            }
+            InitialProfile::File { heights, .. } => heights.clone(),
+        };
+
+        let lowest: f64 = profile.iter().copied().fold(f64::INFINITY, f64::min);
+        profile
+            .iter()
+            .map(|h| (h - lowest).round() as usize)
+            .collect()
+    }
+}
+
+/*
+Functions
+*/
+
+fn file_stem(path: &str) -> &str {
+    Path::new(path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("file")
+}
+
+// Random self-affine profile by Fourier filtering: each mode gets a Gaussian random amplitude with
+//  power spectrum S(k) ~ |k|^-(d + 2 alpha), and the profile is scaled to an interface width of
+//  `amplitude`
+fn rough_profile(
+    n: usize,
+    dimension: usize,
+    alpha: f64,
+    amplitude: f64,
+    rng: &mut Ran2Generator,
+) -> Vec<f64> {
+    let sites: usize = n.pow(dimension as u32);
+
+    // Random amplitude of each mode, leaving out k = 0 which would only shift the mean height
+    let mut modes: Vec<Complex> = (0..sites)
+        .map(|site| {
+            let k2: f64 = wavenumber(site % n, n).powi(2)
+                + if dimension == 2 {
+                    wavenumber(site / n, n).powi(2)
+                } else {
+                    0.0
+                };
+            let scale: f64 = if k2 > 0.0 {
+                k2.powf(-(dimension as f64 + 2.0 * alpha) / 4.0)
+            } else {
+                0.0
+            };
+            (scale * gaussian(rng), scale * gaussian(rng))
+        })
+        .collect();
+
+    // Transform back to real space
+    fft_nd(&mut modes, n, dimension, true);
+
+    // Scale the real part to the requested interface width
+    let profile: Vec<f64> = modes.iter().map(|&(re, _im)| re).collect();
+    let mean: f64 = profile.iter().sum::<f64>() / sites as f64;
+    let width: f64 =
+        (profile.iter().map(|h| (h - mean).powi(2)).sum::<f64>() / sites as f64).sqrt();
+    profile
+        .iter()
+        .map(|h| {
+            if width > 0.0 {
+                (h - mean) * amplitude / width
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+// Standard normal random number, by the Box-Muller transform
+fn gaussian(rng: &mut Ran2Generator) -> f64 {
+    let (u1, u2): (f64, f64) = (rng.next(), rng.next());
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
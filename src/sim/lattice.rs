@@ -0,0 +1,187 @@
+/*
+Library file defining the geometry of the substrate.
+
+The substrate is either a 1D line of L columns, or a 2D L x L lattice of columns with square or
+triangular neighbourhoods. Either way, columns are stored one after another in the surface, so a
+column at (x, y) has index x + L * y, and models find their neighbours through the lattice rather
+than by adding offsets to a column number.
+
+Neighbours are grouped into shells by their distance along the lattice, so that the k-th neighbours
+of a column are all of the columns k steps away from it. On a 1D substrate the shell at distance k
+is just the columns k sites to either side, and on a triangular lattice each column has six nearest
+neighbours rather than four.
+*/
+
+use std::error::Error;
+use std::str::FromStr;
+
+use super::flux::AliasTable;
+use super::models::random_column;
+use super::random::Ran2Generator;
+use super::SimulationParams;
+
+/*
+Enums
+*/
+
+// Names of the lattices which can be supplied in `config_sim.ini`
+pub const LATTICE_NAMES: [&str; 2] = ["square", "triangular"];
+
+// Arrangement of the columns on a 2D substrate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatticeKind {
+    Square,
+    Triangular,
+}
+
+impl LatticeKind {
+    // Name of the lattice, as used in `config_sim.ini` and the output filenames
+    pub fn name(&self) -> &'static str {
+        match self {
+            LatticeKind::Square => "square",
+            LatticeKind::Triangular => "triangular",
+        }
+    }
+
+    // Number of steps along the lattice between a column and one offset from it by (dx, dy). The
+    //  triangular lattice uses axial coordinates, where (1, -1) and (-1, 1) are also neighbours
+    fn distance(&self, dx: i32, dy: i32) -> i32 {
+        match self {
+            LatticeKind::Square => dx.abs() + dy.abs(),
+            LatticeKind::Triangular => dx.abs().max(dy.abs()).max((dx + dy).abs()),
+        }
+    }
+}
+
+impl FromStr for LatticeKind {
+    type Err = Box<dyn Error>;
+
+    fn from_str(name: &str) -> Result<LatticeKind, Box<dyn Error>> {
+        match name {
+            "square" => Ok(LatticeKind::Square),
+            "triangular" => Ok(LatticeKind::Triangular),
+            _ => Err(format!(
+                "{} isn't a valid lattice! Please supply one of {:?}.",
+                name, LATTICE_NAMES
+            )
+            .into()),
+        }
+    }
+}
+
+/*
+Structs
+*/
+
+pub struct Lattice {
+    l: i32,
+    dimension: usize,
+    periodic_bc: [bool; 2],       // whether each axis wraps around
+    shells: Vec<Vec<(i32, i32)>>, // offsets to the columns at each distance 1, 2, 3, etc.
+    flux: Option<AliasTable>,     // landing columns for a non-uniform flux or growth rate
+}
+
+impl Lattice {
+    // Build the lattice for the substrate in `params`, with neighbour shells out to `range` steps
+    pub fn new(params: &SimulationParams, range: i32) -> Lattice {
+        let (l, _t_max) = params.length_t_max;
+
+        let shells: Vec<Vec<(i32, i32)>> = (1..=range)
+            .map(|d| {
+                if params.dimension == 1 {
+                    vec![(-d, 0), (d, 0)]
+                } else {
+                    iproduct!(-d..=d, -d..=d)
+                        .filter(|&(dx, dy)| params.lattice.distance(dx, dy) == d)
+                        .collect()
+                }
+            })
+            .collect();
+
+        // Columns with a higher growth rate catch more of the flux
+        let weights: Option<Vec<f64>> = match (
+            params.flux.weights(l, params.dimension),
+            params.disorder.growth_rates(),
+        ) {
+            (None, None) => None,
+            (weights, rates) => {
+                let mut weights: Vec<f64> = weights.unwrap_or_else(|| vec![1.0; params.sites()]);
+                for (weight, rate) in weights.iter_mut().zip(rates.unwrap_or(&[])) {
+                    *weight *= rate;
+                }
+                Some(weights)
+            }
+        };
+
+        Lattice {
+            l,
+            dimension: params.dimension,
+            periodic_bc: [params.periodic_bc, params.periodic_bc_y],
+            shells,
+            flux: weights.map(|weights| AliasTable::new(&weights)),
+        }
+    }
+
+    // Offsets to each of the columns `d` steps away, for `d` from 1 up to the range of the lattice
+    pub fn shell(&self, d: i32) -> &[(i32, i32)] {
+        &self.shells[d as usize - 1]
+    }
+
+    // Pick a column uniformly at random, one coordinate at a time
+    pub fn random_site(&self, rng: &mut Ran2Generator) -> usize {
+        let mut site: usize = 0;
+        for axis in 0..self.dimension {
+            site += random_column(self.l, rng) as usize * (self.l as usize).pow(axis as u32);
+        }
+        site
+    }
+
+    // Pick the column a new particle lands on, following the flux profile and any quenched growth
+    //  rates. A uniform flux picks columns just like `random_site`
+    pub fn landing_site(&self, rng: &mut Ran2Generator) -> usize {
+        match &self.flux {
+            Some(table) => table.sample(rng),
+            None => self.random_site(rng),
+        }
+    }
+
+    // Index of the column offset by (dx, dy) from column `site`. Periodic boundary conditions wrap
+    //  around the substrate, whereas open boundaries clamp to the column at the edge
+    pub fn neighbour(&self, site: usize, (dx, dy): (i32, i32)) -> usize {
+        let (x, y) = self.coords(site);
+        self.index(
+            self.wrap(x + dx, 0)
+                .unwrap_or_else(|| (x + dx).clamp(0, self.l - 1)),
+            self.wrap(y + dy, 1)
+                .unwrap_or_else(|| (y + dy).clamp(0, self.l - 1)),
+        )
+    }
+
+    // Index of the column offset by (dx, dy) from column `site`, as above, except that open
+    //  boundaries return `None` for columns which fall off the edge of the substrate
+    pub fn column(&self, site: usize, (dx, dy): (i32, i32)) -> Option<usize> {
+        let (x, y) = self.coords(site);
+        Some(self.index(self.wrap(x + dx, 0)?, self.wrap(y + dy, 1)?))
+    }
+
+    fn coords(&self, site: usize) -> (i32, i32) {
+        let l: usize = self.l as usize;
+        ((site % l) as i32, (site / l) as i32)
+    }
+
+    fn index(&self, x: i32, y: i32) -> usize {
+        x as usize + self.l as usize * y as usize
+    }
+
+    // Coordinate `x` along `axis` if it's on the substrate, wrapping it around if that axis is
+    //  periodic
+    fn wrap(&self, x: i32, axis: usize) -> Option<i32> {
+        if self.periodic_bc[axis] {
+            Some(x.rem_euclid(self.l))
+        } else if (0..self.l).contains(&x) {
+            Some(x)
+        } else {
+            None
+        }
+    }
+}
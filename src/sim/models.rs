@@ -0,0 +1,292 @@
+/*
+Library file defining the growth models which can be simulated.
+
+Each model implements the `GrowthModel` trait, which `do_sim` and `run` are generic over. To add a new
+model, give it its own file within `sim/models/`, implement `GrowthModel` for it, and register its
+name in `ModelKind` below so that it can be selected from `config_sim.ini`.
+*/
+
+use std::error::Error;
+use std::str::FromStr;
+
+use super::lattice::Lattice;
+use super::random::Ran2Generator;
+use super::surface::Surface;
+use super::{max, min, SimulationParams};
+
+mod ballistic;
+pub use ballistic::Ballistic;
+
+mod random_deposition;
+pub use random_deposition::RandomDeposition;
+
+mod family;
+pub use family::Family;
+
+mod rsos;
+pub use rsos::Rsos;
+
+mod oblique;
+pub use oblique::Oblique;
+
+mod polyomino;
+pub use polyomino::{gen_shapes, Polyomino, Shape};
+
+mod mixture;
+pub use mixture::Mixture;
+
+mod eden;
+pub use eden::Eden;
+
+mod dla;
+pub use dla::Dla;
+
+mod wolf_villain;
+pub use wolf_villain::WolfVillain;
+
+mod das_sarma;
+pub use das_sarma::DasSarma;
+
+/*
+Traits
+*/
+
+// Rule for adding a single particle to the surface
+pub trait GrowthModel: Send {
+    // Build a fresh instance of the model for one seed of the ensemble
+    fn new(params: &SimulationParams) -> Self
+    where
+        Self: Sized;
+
+    // Deposit a single particle onto the surface `s`
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator);
+
+    // Any extra observables measured by the model, which are averaged over the ensemble and written
+    //  to the .csv after `v`, `h` and `t`. Most models don't measure anything extra
+    fn observables(&self, _s: &Surface) -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+/*
+Enums
+*/
+
+// Names of the models which can be supplied in `config_sim.ini`
+pub const MODEL_NAMES: [&str; 11] = [
+    "ballistic",
+    "random",
+    "family",
+    "rsos",
+    "oblique",
+    "polyomino",
+    "mixture",
+    "eden",
+    "dla",
+    "wv",
+    "dt",
+];
+
+// The growth models available to the simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModelKind {
+    Ballistic,
+    RandomDeposition,
+    Family,
+    Rsos,
+    Oblique,
+    Polyomino,
+    Mixture,
+    Eden,
+    Dla,
+    WolfVillain,
+    DasSarma,
+}
+
+impl ModelKind {
+    // Name of the model, as used in `config_sim.ini` and the output filenames
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModelKind::Ballistic => "ballistic",
+            ModelKind::RandomDeposition => "random",
+            ModelKind::Family => "family",
+            ModelKind::Rsos => "rsos",
+            ModelKind::Oblique => "oblique",
+            ModelKind::Polyomino => "polyomino",
+            ModelKind::Mixture => "mixture",
+            ModelKind::Eden => "eden",
+            ModelKind::Dla => "dla",
+            ModelKind::WolfVillain => "wv",
+            ModelKind::DasSarma => "dt",
+        }
+    }
+
+    // Whether the model can run on a substrate of the given dimension. Models which send particles
+    //  along a path in the plane of the substrate, or drop extended objects, are 1D only
+    pub fn supports_dimension(&self, dimension: usize) -> bool {
+        match self {
+            ModelKind::Oblique | ModelKind::Polyomino => dimension == 1,
+            _ => true,
+        }
+    }
+
+    // Whether particles land on columns drawn from the flux profile. Oblique particles are aimed at
+    //  points rather than columns, polyominoes land across several columns at once, and Eden growth
+    //  has no flux at all
+    pub fn supports_flux(&self) -> bool {
+        !matches!(
+            self,
+            ModelKind::Oblique | ModelKind::Polyomino | ModelKind::Eden
+        )
+    }
+
+    // Whether the model reads the quenched sticking strengths of the substrate. Only `ballistic`
+    //  sticks by chance, and mixtures pass the disorder on to their species, which have to read it
+    pub fn supports_sticking_disorder(&self) -> bool {
+        matches!(self, ModelKind::Ballistic | ModelKind::Mixture)
+    }
+
+    // Whether the model keeps its own record of the deposit, and so would miss any particles added
+    //  or removed by anything other than the model itself
+    pub fn keeps_own_record(&self) -> bool {
+        matches!(self, ModelKind::Eden | ModelKind::Dla)
+    }
+
+    // Whether the model can be one of the species in a mixture
+    pub fn can_mix(&self) -> bool {
+        *self != ModelKind::Mixture && !self.keeps_own_record()
+    }
+
+    // Names of the extra observables measured by the model, in the order `GrowthModel::observables`
+    //  returns them. Mixtures also write those of their species, which are only known per run
+    pub fn observable_names(&self) -> &'static [&'static str] {
+        match self {
+            ModelKind::Rsos => &["rejected"],
+            ModelKind::Oblique => &["lost"],
+            ModelKind::Dla => &["killed"],
+            ModelKind::Mixture => &["fraction_a_deposited", "fraction_a_surface"],
+            _ => &[],
+        }
+    }
+}
+
+impl FromStr for ModelKind {
+    type Err = Box<dyn Error>;
+
+    fn from_str(name: &str) -> Result<ModelKind, Box<dyn Error>> {
+        match name {
+            "ballistic" => Ok(ModelKind::Ballistic),
+            "random" => Ok(ModelKind::RandomDeposition),
+            "family" => Ok(ModelKind::Family),
+            "rsos" => Ok(ModelKind::Rsos),
+            "oblique" => Ok(ModelKind::Oblique),
+            "polyomino" => Ok(ModelKind::Polyomino),
+            "mixture" => Ok(ModelKind::Mixture),
+            "eden" => Ok(ModelKind::Eden),
+            "dla" => Ok(ModelKind::Dla),
+            "wv" => Ok(ModelKind::WolfVillain),
+            "dt" => Ok(ModelKind::DasSarma),
+            _ => Err(format!(
+                "{} isn't a valid growth model! Please supply one of {:?}.",
+                name, MODEL_NAMES
+            )
+            .into()),
+        }
+    }
+}
+
+/*
+Structs
+*/
+
+// Record of every filled site in the deposit, for models which need to know about the overhangs and
+//  voids below the surface as well as the column heights. Height 0 is the substrate
+pub struct Occupancy {
+    filled: Vec<Vec<bool>>,
+}
+
+impl Occupancy {
+    // Take every site up to the top of each column of the surface to be filled
+    pub fn from_surface(s: &Surface) -> Occupancy {
+        Occupancy {
+            filled: s.iter().map(|&h| vec![true; h + 1]).collect(),
+        }
+    }
+
+    pub fn is_filled(&self, c: usize, h: usize) -> bool {
+        self.filled[c].get(h).copied().unwrap_or(false)
+    }
+
+    pub fn fill(&mut self, c: usize, h: usize) {
+        if h >= self.filled[c].len() {
+            self.filled[c].resize(h + 1, false);
+        }
+        self.filled[c][h] = true;
+    }
+}
+
+/*
+Functions
+*/
+
+// Build a model of the given kind behind a pointer, for models which pick between others as they run
+fn build(kind: ModelKind, params: &SimulationParams) -> Box<dyn GrowthModel> {
+    match kind {
+        ModelKind::Ballistic => Box::new(Ballistic::new(params)),
+        ModelKind::RandomDeposition => Box::new(RandomDeposition::new(params)),
+        ModelKind::Family => Box::new(Family::new(params)),
+        ModelKind::Rsos => Box::new(Rsos::new(params)),
+        ModelKind::Oblique => Box::new(Oblique::new(params)),
+        ModelKind::Polyomino => Box::new(Polyomino::new(params)),
+        ModelKind::Eden => Box::new(Eden::new(params)),
+        ModelKind::Dla => Box::new(Dla::new(params)),
+        ModelKind::WolfVillain => Box::new(WolfVillain::new(params)),
+        ModelKind::DasSarma => Box::new(DasSarma::new(params)),
+        ModelKind::Mixture => panic!("Error: a mixture can't contain another mixture!"),
+    }
+}
+
+// Number of bonds a particle landing on top of column `c` would have: one to the site below it, and
+//  one to each nearest neighbour column at least as high as it
+fn bonds(s: &Surface, lattice: &Lattice, c: usize) -> usize {
+    let h: usize = s[c] + 1;
+    1 + lattice
+        .shell(1)
+        .iter()
+        .filter(|&&offset| match lattice.column(c, offset) {
+            Some(n) => s[n] >= h,
+            None => false,
+        })
+        .count()
+}
+
+// Pick a column uniformly at random from a substrate of length `l`
+pub(super) fn random_column(l: i32, rng: &mut Ran2Generator) -> i32 {
+    loop {
+        // Generate a new random column
+        let j = (l as f64 * rng.next()) as i32;
+        if j != l {
+            return j;
+        }
+    }
+}
+
+// Index of the column `offset` sites away from column `j`. Periodic boundary conditions wrap around
+//  the substrate, whereas open boundaries clamp to the column at the edge
+fn neighbour(j: i32, offset: i32, l: i32, periodic_bc: bool) -> usize {
+    if periodic_bc {
+        (j + offset).rem_euclid(l) as usize
+    } else {
+        min(max(j + offset, 0), l - 1) as usize
+    }
+}
+
+// Index of the column `offset` sites away from column `j`, as above, except that open boundaries
+//  return `None` for columns which fall off the edge of the substrate
+fn column(j: i32, offset: i32, l: i32, periodic_bc: bool) -> Option<usize> {
+    if periodic_bc || (0..l).contains(&(j + offset)) {
+        Some(neighbour(j, offset, l, periodic_bc))
+    } else {
+        None
+    }
+}
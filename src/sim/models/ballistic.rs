@@ -0,0 +1,120 @@
+/*
+Ballistic deposition with k-th neighbour sticking.
+
+A particle falls straight down onto column j and sticks at the first point of contact, i.e. at the
+highest of column j + 1 and the tops of the columns within `k_neighbour` steps of it.
+
+With sticking probabilities below 1, the particle only sticks to the side of a neighbouring column
+d sites away with probability p_d as it falls past it, and otherwise carries on falling, at worst to
+the top of its own column. Setting every p_d to 0 recovers random deposition. With quenched
+sticking disorder, p_d is further multiplied by the sticking strength of the column (or site) the
+particle would stick to.
+*/
+
+use super::GrowthModel;
+use crate::sim::disorder::{Disorder, DisorderKind};
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::{max, SimulationParams};
+
+pub struct Ballistic {
+    k_neighbour: i32,
+    lattice: Lattice,
+    p_stick: Vec<f64>,   // sticking probability for each neighbour distance 1..=k
+    always_sticks: bool, // whether every sticking probability is 1
+    disorder: Disorder,  // quenched sticking strength of each column or site
+    contacts: Vec<(usize, f64)>, // heights and sticking probabilities of the neighbours passed
+}
+
+impl GrowthModel for Ballistic {
+    fn new(params: &SimulationParams) -> Ballistic {
+        // Distances beyond the end of the list of sticking probabilities take its last value
+        let last: f64 = *params.sticking_probs.last().unwrap_or(&1.0);
+        let p_stick: Vec<f64> = (0..params.k_neighbour.max(0) as usize)
+            .map(|d| *params.sticking_probs.get(d).unwrap_or(&last))
+            .collect();
+        let always_sticks: bool =
+            p_stick.iter().all(|&p| p >= 1.0) && params.disorder.kind() != DisorderKind::Sticking;
+
+        Ballistic {
+            k_neighbour: params.k_neighbour,
+            lattice: Lattice::new(params, params.k_neighbour),
+            p_stick,
+            always_sticks,
+            disorder: params.disorder.clone(),
+            contacts: Vec::new(),
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        let j: usize = self.lattice.landing_site(rng); // column number
+        let mut h_max: usize = s[j] + 1; // initialize h_max to column j
+
+        if self.always_sticks {
+            // find maximum height of all the neighbour columns and column j
+            for k in 1..=self.k_neighbour {
+                for &offset in self.lattice.shell(k) {
+                    h_max = max(s[self.lattice.neighbour(j, offset)], h_max);
+                }
+            }
+        } else {
+            // find every neighbour the particle falls past before reaching column j. Offsets off
+            //  the edge of an open substrate are skipped, rather than trying the edge column again
+            //  with the sticking probability of a farther distance
+            self.contacts.clear();
+            for k in 1..=self.k_neighbour {
+                for &offset in self.lattice.shell(k) {
+                    let n: usize = match self.lattice.column(j, offset) {
+                        Some(n) => n,
+                        None => continue,
+                    };
+                    let h: usize = s[n];
+                    let p: f64 = self.p_stick[k as usize - 1] * self.disorder.sticking(n, h);
+                    if h > h_max && p > 0.0 {
+                        self.contacts.push((h, p));
+                    }
+                }
+            }
+
+            // try to stick to each of them in turn, from the top down
+            self.contacts
+                .sort_unstable_by(|a, b| b.0.cmp(&a.0).then(b.1.total_cmp(&a.1)));
+            for &(h, p) in &self.contacts {
+                if p >= 1.0 || rng.next() < p {
+                    h_max = h;
+                    break;
+                }
+            }
+        }
+        // set column j to new height
+        s.place(j, h_max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::models::ModelKind;
+
+    #[test]
+    fn open_edges_add_no_extra_contacts() {
+        // On two columns with open boundaries, second neighbours are always off the edge, and
+        //  nearest neighbours never stick, so this is random deposition: every particle should
+        //  raise its own column by exactly one site
+        let params: SimulationParams = SimulationParams {
+            k_neighbour: 2,
+            sticking_probs: vec![0.0, 1.0],
+            periodic_bc: false,
+            ..SimulationParams::for_tests(ModelKind::Ballistic, 2)
+        };
+        let mut ballistic: Ballistic = Ballistic::new(&params);
+        let mut s: Surface = Surface::new(vec![0; 2], false);
+        let mut rng: Ran2Generator = Ran2Generator::new(-5);
+
+        for n in 1..=1_000 {
+            ballistic.deposit(&mut s, &mut rng);
+            assert_eq!(s.iter().sum::<usize>(), n);
+        }
+    }
+}
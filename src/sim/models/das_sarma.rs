@@ -0,0 +1,61 @@
+/*
+Das Sarma-Tamborenea (DT) model of molecular-beam epitaxy.
+
+A particle lands on top of column j. If it has a bond to a nearest neighbour column at least as high
+as itself it stays put, otherwise it looks for a kink site: a column within `diffusion_length` steps
+of it where it would have at least one such bond. It moves to the nearest kink site, picking between
+equally near ones at random, and stays put if there are none.
+
+Unlike the Wolf-Villain model, particles only ever move to increase their bonds from one to more
+than one, and don't seek out the column with the most bonds. Particles are never lost, so the
+volume of the deposit is conserved by the relaxation.
+*/
+
+use super::{bonds, random_column, GrowthModel};
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct DasSarma {
+    diffusion_length: i32,
+    lattice: Lattice,
+    kinks: Vec<usize>, // kink sites at the current distance from column j
+}
+
+impl GrowthModel for DasSarma {
+    fn new(params: &SimulationParams) -> DasSarma {
+        DasSarma {
+            diffusion_length: params.diffusion_length,
+            lattice: Lattice::new(params, params.diffusion_length.max(1)),
+            kinks: Vec::new(),
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        let j: usize = self.lattice.landing_site(rng); // column number
+        let mut landing: usize = j;
+
+        // Only particles without a lateral bond move
+        if bonds(s, &self.lattice, j) == 1 {
+            for k in 1..=self.diffusion_length {
+                self.kinks.clear();
+                for &offset in self.lattice.shell(k) {
+                    if let Some(i) = self.lattice.column(j, offset) {
+                        if bonds(s, &self.lattice, i) > 1 {
+                            self.kinks.push(i);
+                        }
+                    }
+                }
+
+                // Move to the nearest kink site
+                if !self.kinks.is_empty() {
+                    landing = self.kinks[random_column(self.kinks.len() as i32, rng) as usize];
+                    break;
+                }
+            }
+        }
+        // land on top of the chosen column
+        s.place(landing, s[landing] + 1);
+    }
+}
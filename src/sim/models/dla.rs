@@ -0,0 +1,111 @@
+/*
+Diffusion-limited deposition onto the substrate (DLA-on-a-line).
+
+Rather than falling straight down, each particle is launched `dla_launch_margin` sites above the
+highest point of the deposit, over a random column, and random-walks on the lattice (up, down, or
+to any nearest neighbour column) until it shares a face with the deposit or the substrate, where
+it sticks. Particles which wander more than `dla_kill_radius` sites above the highest point of the
+deposit are killed and a new one is launched in their place. This gives the dendritic films that
+ballistic deposition is often compared against.
+
+Time counts the number of particles which stick per site, and the number of killed particles per
+site is reported as an extra observable. `k_neighbour` has no effect on this model.
+*/
+
+use super::{random_column, GrowthModel, Occupancy};
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Dla {
+    sites: usize,
+    lattice: Lattice,
+    launch_margin: usize,
+    kill_radius: usize,
+    filled: Option<Occupancy>, // built from the surface on the first deposit
+    h_top: usize,              // height of the highest filled site
+    killed: u64,               // total number of particles killed
+}
+
+impl GrowthModel for Dla {
+    fn new(params: &SimulationParams) -> Dla {
+        Dla {
+            sites: params.sites(),
+            lattice: Lattice::new(params, 1),
+            launch_margin: params.dla_launch_margin,
+            kill_radius: params.dla_kill_radius,
+            filled: None,
+            h_top: 0,
+            killed: 0,
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        if self.filled.is_none() {
+            self.filled = Some(Occupancy::from_surface(s));
+            self.h_top = s.iter().copied().max().unwrap_or(0);
+        }
+        let filled: &mut Occupancy = self.filled.as_mut().unwrap();
+
+        // Walkers can step to any nearest neighbour column, or up, or down
+        let n_lateral: usize = self.lattice.shell(1).len();
+        let n_steps: i32 = n_lateral as i32 + 2;
+
+        loop {
+            // Launch a new walker above a column drawn from the flux
+            let mut c: usize = self.lattice.landing_site(rng);
+            let mut h: usize = self.h_top + self.launch_margin;
+
+            loop {
+                // Stick as soon as the walker shares a face with the deposit. Nothing can touch it
+                //  more than one site above the highest point
+                if h <= self.h_top + 1 && touching(filled, &self.lattice, c, h) {
+                    filled.fill(c, h);
+                    s.place(c, h);
+                    self.h_top = self.h_top.max(h);
+                    return;
+                }
+
+                // Otherwise take a step. Steps off the edge of a substrate with open boundaries
+                //  leave the walker where it is
+                let step: usize = random_column(n_steps, rng) as usize;
+                if step < n_lateral {
+                    if let Some(n) = self.lattice.column(c, self.lattice.shell(1)[step]) {
+                        c = n;
+                    }
+                } else if step == n_lateral {
+                    h += 1;
+                    if h > self.h_top + self.kill_radius {
+                        self.killed += 1;
+                        break;
+                    }
+                } else {
+                    h -= 1;
+                }
+            }
+        }
+    }
+
+    fn observables(&self, _s: &Surface) -> Vec<f64> {
+        // Killed particles per site, in the same units as `t`
+        vec![self.killed as f64 / self.sites as f64]
+    }
+}
+
+/*
+Functions
+*/
+
+// Whether the site at height `h` in column `c` shares a face with a filled site
+fn touching(filled: &Occupancy, lattice: &Lattice, c: usize, h: usize) -> bool {
+    filled.is_filled(c, h - 1)
+        || filled.is_filled(c, h + 1)
+        || lattice
+            .shell(1)
+            .iter()
+            .any(|&offset| match lattice.column(c, offset) {
+                Some(n) => filled.is_filled(n, h),
+                None => false,
+            })
+}
@@ -0,0 +1,108 @@
+/*
+Eden growth on the substrate.
+
+Rather than dropping particles down the columns, the deposit grows as a cluster from the substrate:
+each new site is picked uniformly at random from the perimeter of the cluster, i.e. the empty sites
+which share a face with a filled site (or with the substrate). Sites can therefore fill in underneath
+overhangs as well as on top of the columns. The model belongs to the KPZ universality class, like
+ballistic deposition, but with different finite-size corrections.
+
+Time counts the number of sites added per column, and `k_neighbour` has no effect on this model.
+*/
+
+use std::collections::HashMap;
+
+use super::{random_column, GrowthModel, Occupancy};
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Eden {
+    sites: usize,
+    lattice: Lattice,
+    filled: Option<Occupancy>, // built from the surface on the first deposit
+    perimeter: Perimeter,
+}
+
+// Empty sites which share a face with the cluster, stored so that they can be picked at random and
+//  removed in constant time
+struct Perimeter {
+    sites: Vec<(usize, usize)>, // column and height of each perimeter site
+    index: HashMap<(usize, usize), usize>, // position of each perimeter site in `sites`
+}
+
+impl GrowthModel for Eden {
+    fn new(params: &SimulationParams) -> Eden {
+        Eden {
+            sites: params.sites(),
+            lattice: Lattice::new(params, 1),
+            filled: None,
+            perimeter: Perimeter {
+                sites: Vec::new(),
+                index: HashMap::new(),
+            },
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        if self.filled.is_none() {
+            self.start(s);
+        }
+        let filled: &mut Occupancy = self.filled.as_mut().unwrap();
+
+        // Pick a perimeter site uniformly and fill it
+        let i: usize = random_column(self.perimeter.sites.len() as i32, rng) as usize;
+        let (c, h) = self.perimeter.remove(i);
+        filled.fill(c, h);
+        s.place(c, h);
+
+        // Any empty sites sharing a face with it join the perimeter
+        self.perimeter.add(filled, c, h + 1);
+        if h > 1 {
+            self.perimeter.add(filled, c, h - 1);
+        }
+        for &offset in self.lattice.shell(1) {
+            if let Some(n) = self.lattice.column(c, offset) {
+                self.perimeter.add(filled, n, h);
+            }
+        }
+    }
+}
+
+impl Eden {
+    // Take the cluster to be every site up to the top of each column of the starting surface, and
+    //  find its perimeter
+    fn start(&mut self, s: &Surface) {
+        let filled: Occupancy = Occupancy::from_surface(s);
+        for c in 0..self.sites {
+            self.perimeter.add(&filled, c, s[c] + 1);
+            for &offset in self.lattice.shell(1) {
+                if let Some(n) = self.lattice.column(c, offset) {
+                    for h in s[n] + 1..=s[c] {
+                        self.perimeter.add(&filled, n, h);
+                    }
+                }
+            }
+        }
+        self.filled = Some(filled);
+    }
+}
+
+impl Perimeter {
+    fn add(&mut self, filled: &Occupancy, c: usize, h: usize) {
+        if !filled.is_filled(c, h) && !self.index.contains_key(&(c, h)) {
+            self.index.insert((c, h), self.sites.len());
+            self.sites.push((c, h));
+        }
+    }
+
+    fn remove(&mut self, i: usize) -> (usize, usize) {
+        let site: (usize, usize) = self.sites.swap_remove(i);
+        self.index.remove(&site);
+        if let Some(&moved) = self.sites.get(i) {
+            self.index.insert(moved, i);
+        }
+        site
+    }
+}
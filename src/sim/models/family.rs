@@ -0,0 +1,59 @@
+/*
+Random deposition with surface relaxation (the Family model).
+
+A particle falls straight down onto column j and then relaxes to the lowest column within
+`k_neighbour` steps of it along the lattice. If column j is already (joint) lowest the particle
+stays put, otherwise any ties between the lowest neighbours are broken at random. The model belongs
+to the Edwards-Wilkinson universality class.
+*/
+
+use super::GrowthModel;
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Family {
+    k_neighbour: i32,
+    lattice: Lattice,
+}
+
+impl GrowthModel for Family {
+    fn new(params: &SimulationParams) -> Family {
+        Family {
+            k_neighbour: params.k_neighbour,
+            lattice: Lattice::new(params, params.k_neighbour),
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        let j: usize = self.lattice.landing_site(rng); // column number
+
+        // Start at column j, which wins any ties with its neighbours
+        let mut lowest: usize = j;
+        let mut n_lowest: u32 = 0; // number of neighbours tied for lowest
+
+        // find the lowest of all the neighbour columns and column j
+        for k in 1..=self.k_neighbour {
+            for &offset in self.lattice.shell(k) {
+                let i = match self.lattice.column(j, offset) {
+                    Some(i) => i,
+                    None => continue,
+                };
+
+                if s[i] < s[lowest] {
+                    lowest = i;
+                    n_lowest = 1;
+                } else if s[i] == s[lowest] && n_lowest > 0 {
+                    // Tied with the current lowest neighbour, so pick between them uniformly
+                    n_lowest += 1;
+                    if rng.next() * (n_lowest as f64) < 1.0 {
+                        lowest = i;
+                    }
+                }
+            }
+        }
+        // land on top of the lowest column
+        s.place(lowest, s[lowest] + 1);
+    }
+}
@@ -0,0 +1,90 @@
+/*
+Two-species (impurity) deposition.
+
+Each particle is of species A with probability `mixture_p`, and of species B otherwise. Each species
+is deposited by its own growth model, with its own `k_neighbour` reach, so that e.g. ballistic
+deposition can be mixed with random deposition to study the crossover between the two.
+
+The surface keeps track of which species filled each site, and the model reports the fraction of
+the deposit made up of species A, and the fraction of the columns with species A on top, as extra
+observables. These are followed by any extra observables measured by species A and then species B.
+*/
+
+use super::{build, GrowthModel};
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Mixture {
+    p: f64,                             // probability that a particle is of species A
+    species: Vec<Box<dyn GrowthModel>>, // growth models for species A and B
+}
+
+impl GrowthModel for Mixture {
+    fn new(params: &SimulationParams) -> Mixture {
+        // Build each species' model with its own parameters
+        let species: Vec<Box<dyn GrowthModel>> = params
+            .mixture_models
+            .iter()
+            .zip(params.mixture_k_neighbours.iter())
+            .map(|(&model, &k_neighbour)| {
+                let species_params = SimulationParams {
+                    model,
+                    k_neighbour,
+                    ..params.clone()
+                };
+                build(model, &species_params)
+            })
+            .collect();
+
+        Mixture {
+            p: params.mixture_p,
+            species,
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        // Pick the species of this particle, then let its own model deposit it
+        let species: usize = if rng.next() < self.p { 0 } else { 1 };
+
+        s.set_species(species);
+        self.species[species].deposit(s, rng);
+    }
+
+    fn observables(&self, s: &Surface) -> Vec<f64> {
+        let mut observables: Vec<f64> = vec![s.fraction_deposited(0), s.fraction_at_surface(0)];
+        for model in &self.species {
+            observables.extend(model.observables(s));
+        }
+        observables
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::models::ModelKind;
+
+    #[test]
+    fn oblique_species_fires_above_columns_grown_by_the_other() {
+        // With no sideways reach, and oblique particles fired straight down, both species are
+        //  random deposition, so every particle should raise a single column by exactly one site.
+        //  Ballistic particles keep raising columns above the last height oblique deposited at
+        let params: SimulationParams = SimulationParams {
+            mixture_models: [ModelKind::Ballistic, ModelKind::Oblique],
+            mixture_k_neighbours: [0, 0],
+            mixture_p: 0.8,
+            track_bulk: true,
+            ..SimulationParams::for_tests(ModelKind::Mixture, 8)
+        };
+        let mut mixture: Mixture = Mixture::new(&params);
+        let mut s: Surface = Surface::new(vec![0; 8], true);
+        let mut rng: Ran2Generator = Ran2Generator::new(-3);
+
+        for n in 1..=2_000 {
+            mixture.deposit(&mut s, &mut rng);
+            assert_eq!(s.iter().sum::<usize>(), n);
+        }
+        assert_eq!(s.density(), 1.0);
+    }
+}
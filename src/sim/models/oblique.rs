@@ -0,0 +1,212 @@
+/*
+Ballistic deposition at oblique incidence.
+
+Instead of dropping straight down, each particle travels in a straight line at an angle `theta` to
+the vertical, aimed at a random position along the substrate. At every lattice site it passes
+through, it sticks if the site beneath it or the top of a column within `k_neighbour` sites either
+side of it is at the same height, exactly as in `Ballistic`. A particle also sticks if its path would
+take it into the side of a column. Particles always stick on contact, so sticking probabilities and
+sticking disorder aren't supported. With `theta = 0` this is identical to `Ballistic`.
+
+The angle is either fixed, or drawn uniformly from `[theta - spread, theta + spread]` for each
+particle. Positive angles travel towards increasing column numbers. With open boundaries, particles
+which leave the side of the substrate before sticking (or which hit the outside face of the end
+columns) are lost. Time counts every particle fired, so the clock still advances once the upwind edge
+shadows the rest of the substrate. The number of lost particles per site is reported as an extra
+observable.
+*/
+
+use super::{column, GrowthModel};
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Oblique {
+    l: i32,
+    k_neighbour: i32,
+    periodic_bc: bool,
+    theta: f64,   // mean angle to the vertical, in radians
+    spread: f64,  // half-width of the distribution of angles, in radians
+    h_top: usize, // height of the highest column, just above which particles are fired
+    lost: u64,    // total number of particles lost off the side of the substrate
+}
+
+impl Oblique {
+    // Index of column `cu` (which isn't wrapped around periodic boundaries), or `None` if it is off
+    //  the edge of the substrate
+    fn wrap(&self, cu: i64) -> Option<usize> {
+        if self.periodic_bc {
+            Some(cu.rem_euclid(self.l as i64) as usize)
+        } else if (0..self.l as i64).contains(&cu) {
+            Some(cu as usize)
+        } else {
+            None
+        }
+    }
+
+    // Highest height at which a particle in column `c` is in contact with the deposit, either
+    //  resting on top of column `c` or beside the top of one of its neighbours
+    fn contact_height(&self, s: &[usize], c: usize) -> usize {
+        let mut h_max: usize = s[c] + 1;
+        for k in 1..=self.k_neighbour {
+            for offset in [-k, k] {
+                if let Some(i) = column(c as i32, offset, self.l, self.periodic_bc) {
+                    h_max = h_max.max(s[i]);
+                }
+            }
+        }
+        h_max
+    }
+
+    // Fire a single particle along its path, returning the column and height it sticks at, or
+    //  `None` if it leaves the side of the substrate first
+    fn fire(&self, s: &[usize], rng: &mut Ran2Generator) -> Option<(usize, usize)> {
+        // Draw the angle for this particle, then its horizontal step per unit height
+        let theta: f64 = if self.spread > 0.0 {
+            self.theta + self.spread * (2.0 * rng.next() - 1.0)
+        } else {
+            self.theta
+        };
+        let dx: f64 = theta.tan();
+
+        // Fire from just above the highest column, aiming at a uniformly random point along the
+        //  bottom of the substrate, so that the flux is uniform across it
+        let h_fire: usize = self.h_top + 1;
+        let x0: f64 = self.l as f64 * rng.next() - h_fire as f64 * dx;
+
+        self.trace(s, dx, x0)
+    }
+
+    // Follow a particle fired from position `x0` just above the highest column, moving `dx` columns
+    //  sideways per unit height, returning the column and height it sticks at as in `fire`
+    fn trace(&self, s: &[usize], dx: f64, x0: f64) -> Option<(usize, usize)> {
+        let step: i64 = if dx > 0.0 { 1 } else { -1 };
+        let h_fire: usize = self.h_top + 1;
+
+        // The path passes down through each column in turn. Any particle which starts off the side
+        //  of the substrate (with open boundaries) is treated as being in a single column beside it
+        let mut cu: i64 = (x0.floor() as i64).clamp(-1, self.l as i64); // current column
+        let mut y: usize = h_fire; // current height
+
+        loop {
+            // Lowest height the path reaches in this column before crossing into the next one
+            let levels: f64 = if dx > 0.0 {
+                (((cu + 1) as f64 - x0) / dx).ceil() - 1.0
+            } else if dx < 0.0 {
+                ((x0 - cu as f64) / -dx).floor()
+            } else {
+                f64::INFINITY
+            };
+            let y_last: usize = (h_fire as f64 - levels).clamp(1.0, y as f64) as usize;
+
+            match self.wrap(cu) {
+                Some(c) => {
+                    // Stick at the first height on the way down which touches the deposit
+                    let h_contact: usize = self.contact_height(s, c);
+                    if h_contact >= y_last {
+                        return Some((c, y.min(h_contact)));
+                    }
+                }
+                None => {
+                    // Off the side of the substrate, and moving away from it
+                    if (cu < 0) == (step < 0) {
+                        return None;
+                    }
+                }
+            }
+
+            // Cross into the next column, sticking if the path runs into the side of it
+            let cu_next: i64 = cu + step;
+            if let Some(c_next) = self.wrap(cu_next) {
+                if s[c_next] >= y_last {
+                    return self.wrap(cu).map(|c| (c, y_last));
+                }
+            }
+            cu = cu_next;
+            y = y_last;
+        }
+    }
+}
+
+impl GrowthModel for Oblique {
+    fn new(params: &SimulationParams) -> Oblique {
+        let (l, _t_max) = params.length_t_max;
+
+        Oblique {
+            l,
+            k_neighbour: params.k_neighbour,
+            periodic_bc: params.periodic_bc,
+            theta: params.oblique_angle.to_radians(),
+            spread: params.oblique_spread.to_radians(),
+            h_top: 0,
+            lost: 0,
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        // Make sure particles start above the surface, however the surface got there, including
+        //  any columns grown by the other species of a mixture
+        self.h_top = s.iter().copied().max().unwrap_or(0);
+
+        match self.fire(s, rng) {
+            // set column c to new height
+            Some((c, y)) => s.place(c, y),
+            None => self.lost += 1,
+        }
+    }
+
+    fn observables(&self, _s: &Surface) -> Vec<f64> {
+        // Particles lost off the side of the substrate per site, in the same units as `t`
+        vec![self.lost as f64 / self.l as f64]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::models::{Ballistic, ModelKind};
+
+    #[test]
+    fn vertical_incidence_matches_ballistic() {
+        // Both models draw a single random number per particle for the landing column, so the same
+        //  seed should build the same surface
+        let params: SimulationParams = SimulationParams::for_tests(ModelKind::Oblique, 32);
+        let mut oblique: Oblique = Oblique::new(&params);
+        let mut ballistic: Ballistic = Ballistic::new(&params);
+        let mut s_oblique: Surface = Surface::new(vec![0; 32], false);
+        let mut s_ballistic: Surface = Surface::new(vec![0; 32], false);
+        let mut rng_oblique: Ran2Generator = Ran2Generator::new(-7);
+        let mut rng_ballistic: Ran2Generator = Ran2Generator::new(-7);
+
+        for _ in 0..10_000 {
+            oblique.deposit(&mut s_oblique, &mut rng_oblique);
+            ballistic.deposit(&mut s_ballistic, &mut rng_ballistic);
+        }
+        assert_eq!(&s_oblique[..], &s_ballistic[..]);
+    }
+
+    #[test]
+    fn diagonal_path_sticks_beside_wall() {
+        // A wall of height 3 in column 5 of an otherwise empty substrate, so particles are fired
+        //  from a height of 4
+        let params: SimulationParams = SimulationParams {
+            periodic_bc: false,
+            ..SimulationParams::for_tests(ModelKind::Oblique, 8)
+        };
+        let mut oblique: Oblique = Oblique::new(&params);
+        let s: Vec<usize> = vec![0, 0, 0, 0, 0, 3, 0, 0];
+        oblique.h_top = 3;
+
+        // At 45 degrees from x = 1.5, the particle passes through column 1 at height 4, column 2 at
+        //  height 3 and column 3 at height 2, then crosses into column 4 at height 2, where it
+        //  touches the side of the wall
+        assert_eq!(oblique.trace(&s, 1.0, 1.5), Some((4, 2)));
+
+        // Fired from the same point the other way, it travels away from the wall and leaves the
+        //  substrate before reaching the bottom
+        assert_eq!(oblique.trace(&s, -1.0, 1.5), None);
+
+        // Straight down, it lands on top of column 1
+        assert_eq!(oblique.trace(&s, 0.0, 1.5), Some((1, 1)));
+    }
+}
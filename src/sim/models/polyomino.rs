@@ -0,0 +1,261 @@
+/*
+Ballistic deposition of polyominoes, e.g. tetrominoes.
+
+Each falling object is a polyomino picked uniformly from a configurable set of shapes, in one of its
+distinct orientations picked uniformly at random, above a random column. It falls straight down and
+sticks at the first point of contact, i.e. as soon as any of its cells would rest on top of a column,
+or sit beside the top of a column within `k_neighbour` sites of it, exactly as a single particle
+does in `Ballistic`, though always with certainty, so sticking probabilities and sticking disorder
+aren't supported. Each column then takes the height of the highest cell which landed in it.
+
+Time counts the number of objects deposited per site, rather than the number of cells.
+
+Shapes are given in `config_sim.ini` by name:
+    - `tetrominoes`: All seven tetrominoes, or any one of them by letter (`i`, `o`, `t`, `s`, `z`,
+        `j` or `l`)
+    - `monomer`, `dimer`: A single block, or two blocks side by side
+    - `rectWxH`: A W by H rectangle, e.g. `rect3x2`
+and may also be read from a file, where `#` marks a filled cell, any other character an empty one,
+and shapes are separated by blank lines.
+*/
+
+use std::error::Error;
+use std::fs;
+
+use super::{column, neighbour, random_column, GrowthModel};
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+// Cells making up a polyomino, as (column, height) offsets from its bottom-left corner
+pub type Shape = Vec<(i32, i32)>;
+
+pub struct Polyomino {
+    l: i32,
+    k_neighbour: i32,
+    periodic_bc: bool,
+    orientations: Vec<Vec<Shape>>, // distinct orientations of each shape
+}
+
+impl GrowthModel for Polyomino {
+    fn new(params: &SimulationParams) -> Polyomino {
+        let (l, _t_max) = params.length_t_max;
+
+        let orientations: Vec<Vec<Shape>> = params.polyomino_shapes.iter().map(rotations).collect();
+
+        // Every orientation of every shape needs to fit on the substrate
+        for shape in orientations.iter().flatten() {
+            if width(shape) > l {
+                panic!(
+                    "Error: a polyomino {} columns wide doesn't fit on a substrate of length {}!",
+                    width(shape),
+                    l
+                );
+            }
+        }
+
+        Polyomino {
+            l,
+            k_neighbour: params.k_neighbour,
+            periodic_bc: params.periodic_bc,
+            orientations,
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        // Pick the shape, then its orientation
+        let shapes: &Vec<Shape> = &self.orientations[pick(self.orientations.len(), rng)];
+        let shape: &Shape = &shapes[pick(shapes.len(), rng)];
+
+        // Pick the column of its left-hand edge. With open boundaries, the whole shape has to land
+        //  on the substrate
+        let j: i32 = if self.periodic_bc {
+            random_column(self.l, rng)
+        } else {
+            random_column(self.l - width(shape) + 1, rng)
+        };
+
+        // Find the height of the bottom of the shape when any of its cells first touches the deposit
+        let mut y_stop: usize = 0;
+        for &(dx, dy) in shape {
+            let x: i32 = neighbour(j, dx, self.l, self.periodic_bc) as i32;
+            let mut h_contact: usize = s[x as usize] + 1;
+            for k in 1..=self.k_neighbour {
+                for offset in [-k, k] {
+                    if let Some(i) = column(x, offset, self.l, self.periodic_bc) {
+                        h_contact = h_contact.max(s[i]);
+                    }
+                }
+            }
+            y_stop = y_stop.max(h_contact.saturating_sub(dy as usize));
+        }
+
+        // Fill each of the shape's cells, so each column it landed in takes the height of its
+        //  highest cell
+        for &(dx, dy) in shape {
+            s.place(
+                neighbour(j, dx, self.l, self.periodic_bc),
+                y_stop + dy as usize,
+            );
+        }
+    }
+}
+
+/*
+Functions
+*/
+
+// Pick an index uniformly from `0..n`, without drawing a random number if there's only one choice
+fn pick(n: usize, rng: &mut Ran2Generator) -> usize {
+    if n == 1 {
+        0
+    } else {
+        random_column(n as i32, rng) as usize
+    }
+}
+
+// Number of columns spanned by a shape
+fn width(shape: &Shape) -> i32 {
+    shape.iter().map(|&(dx, _)| dx).max().unwrap_or(0) + 1
+}
+
+// Shift a shape so that its bottom-left corner sits at (0, 0), with its cells in a consistent order
+fn normalise(shape: &Shape) -> Shape {
+    let dx_min: i32 = shape.iter().map(|&(dx, _)| dx).min().unwrap_or(0);
+    let dy_min: i32 = shape.iter().map(|&(_, dy)| dy).min().unwrap_or(0);
+
+    let mut normalised: Shape = shape
+        .iter()
+        .map(|&(dx, dy)| (dx - dx_min, dy - dy_min))
+        .collect();
+    normalised.sort_unstable();
+    normalised
+}
+
+// All of the distinct quarter-turn rotations of a shape
+fn rotations(shape: &Shape) -> Vec<Shape> {
+    let mut distinct: Vec<Shape> = Vec::new();
+    let mut rotated: Shape = normalise(shape);
+
+    for _ in 0..4 {
+        if !distinct.contains(&rotated) {
+            distinct.push(rotated.clone());
+        }
+        rotated = normalise(&rotated.iter().map(|&(dx, dy)| (dy, -dx)).collect());
+    }
+    distinct
+}
+
+// Check that every cell of a shape is connected to the rest of it through its edges
+fn is_connected(shape: &Shape) -> bool {
+    let mut reached: Vec<(i32, i32)> = vec![shape[0]];
+    let mut i: usize = 0;
+
+    while i < reached.len() {
+        let (dx, dy) = reached[i];
+        for cell in [(dx - 1, dy), (dx + 1, dy), (dx, dy - 1), (dx, dy + 1)] {
+            if shape.contains(&cell) && !reached.contains(&cell) {
+                reached.push(cell);
+            }
+        }
+        i += 1;
+    }
+    reached.len() == shape.len()
+}
+
+// Build a shape from a list of strings, where the first string is the top row of the shape
+fn from_rows(rows: &[&str]) -> Shape {
+    let height: i32 = rows.len() as i32;
+
+    let mut shape: Shape = Vec::new();
+    for (row, line) in rows.iter().enumerate() {
+        for (dx, cell) in line.chars().enumerate() {
+            if cell == '#' {
+                shape.push((dx as i32, height - 1 - row as i32));
+            }
+        }
+    }
+    normalise(&shape)
+}
+
+// Look up the shape(s) for a single name in `config_sim.ini`
+fn shapes_from_name(name: &str) -> Result<Vec<Shape>, Box<dyn Error>> {
+    let shapes: Vec<Shape> = match name {
+        "tetrominoes" => ["i", "o", "t", "s", "z", "j", "l"]
+            .iter()
+            .map(|letter| shapes_from_name(letter).map(|mut shape| shape.remove(0)))
+            .collect::<Result<Vec<Shape>, Box<dyn Error>>>()?,
+        "i" => vec![from_rows(&["####"])],
+        "o" => vec![from_rows(&["##", "##"])],
+        "t" => vec![from_rows(&["###", ".#."])],
+        "s" => vec![from_rows(&[".##", "##."])],
+        "z" => vec![from_rows(&["##.", ".##"])],
+        "j" => vec![from_rows(&["#..", "###"])],
+        "l" => vec![from_rows(&["..#", "###"])],
+        "monomer" => vec![from_rows(&["#"])],
+        "dimer" => vec![from_rows(&["##"])],
+        _ if name.starts_with("rect") => {
+            let (w, h) = name[4..]
+                .split_once('x')
+                .ok_or(format!("{} isn't a valid rectangle! Please supply e.g. rect3x2.", name))?;
+            let (w, h): (usize, usize) = (w.parse()?, h.parse()?);
+            if w == 0 || h == 0 {
+                return Err(format!("{} isn't a valid rectangle! Please supply a non-zero width and height.", name).into());
+            }
+            let row: String = "#".repeat(w);
+            vec![from_rows(&vec![&row[..]; h])]
+        }
+        _ => {
+            return Err(format!(
+                "{} isn't a valid polyomino! Please supply one of `tetrominoes`, `i`, `o`, `t`, `s`, `z`, `j`, `l`, `monomer`, `dimer` or `rectWxH`.",
+                name
+            )
+            .into())
+        }
+    };
+    Ok(shapes)
+}
+
+// Read shapes drawn in a file, separated by blank lines
+fn shapes_from_file(path: &str) -> Result<Vec<Shape>, Box<dyn Error>> {
+    let contents: String = fs::read_to_string(path)?;
+
+    let mut shapes: Vec<Shape> = Vec::new();
+    let mut rows: Vec<&str> = Vec::new();
+    for line in contents.lines().chain(std::iter::once("")) {
+        if line.trim().is_empty() {
+            if !rows.is_empty() {
+                shapes.push(from_rows(&rows));
+                rows.clear();
+            }
+        } else {
+            rows.push(line);
+        }
+    }
+    Ok(shapes)
+}
+
+// Generate the full set of shapes from the names and (optional) file given in `config_sim.ini`
+pub fn gen_shapes(names: &[String], file: &str) -> Result<Vec<Shape>, Box<dyn Error>> {
+    let mut shapes: Vec<Shape> = Vec::new();
+    for name in names.iter().filter(|name| !name.is_empty()) {
+        shapes.extend(shapes_from_name(name)?);
+    }
+    if !file.is_empty() {
+        shapes.extend(shapes_from_file(file)?);
+    }
+
+    if shapes.is_empty() {
+        return Err("No polyominoes were supplied!".into());
+    }
+    for shape in &shapes {
+        if shape.is_empty() || !is_connected(shape) {
+            return Err(format!(
+                "The polyomino with cells {:?} isn't connected! Please make sure every cell shares an edge with another.",
+                shape
+            )
+            .into());
+        }
+    }
+    Ok(shapes)
+}
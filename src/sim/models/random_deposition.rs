@@ -0,0 +1,32 @@
+/*
+Random deposition (RD).
+
+A particle falls straight down onto column j and lands on top of it, with no interaction between
+neighbouring columns. The columns therefore grow independently, the width grows as t^(1/2) and the
+surface never saturates, which makes it a useful null model for the rest of the pipeline.
+*/
+
+use super::GrowthModel;
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct RandomDeposition {
+    lattice: Lattice,
+}
+
+impl GrowthModel for RandomDeposition {
+    fn new(params: &SimulationParams) -> RandomDeposition {
+        RandomDeposition {
+            lattice: Lattice::new(params, 0),
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        let j: usize = self.lattice.landing_site(rng); // column number
+
+        // land on top of column j
+        s.place(j, s[j] + 1);
+    }
+}
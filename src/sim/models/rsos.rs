@@ -0,0 +1,71 @@
+/*
+Restricted solid-on-solid (RSOS) deposition, as introduced by Kim and Kosterlitz.
+
+A particle falls straight down onto column j and lands on top of it, unless doing so would leave a
+height difference of more than `rsos_max_step` between column j and any of its nearest
+neighbours, in which case the attempt is rejected. The model belongs to the KPZ universality class,
+with much smaller corrections to scaling than ballistic deposition.
+
+Rejected attempts are counted, so that time may be defined either per attempt (the default, where a
+rejected attempt still advances the clock) or per accepted particle (where the model keeps trying
+until a particle sticks). The number of rejected attempts per site is reported as an extra
+observable, so either definition of time can be recovered from the output.
+*/
+
+use super::GrowthModel;
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct Rsos {
+    sites: usize,
+    lattice: Lattice,
+    max_step: usize,
+    time_per_attempt: bool,
+    rejected: u64, // total number of rejected attempts
+}
+
+impl GrowthModel for Rsos {
+    fn new(params: &SimulationParams) -> Rsos {
+        Rsos {
+            sites: params.sites(),
+            lattice: Lattice::new(params, 1),
+            max_step: params.rsos_max_step,
+            time_per_attempt: params.rsos_time_per_attempt,
+            rejected: 0,
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        loop {
+            let j: usize = self.lattice.landing_site(rng); // column number
+            let h_new: usize = s[j] + 1;
+
+            // Check the step to each nearest neighbour stays within the allowed height difference
+            let allowed =
+                self.lattice
+                    .shell(1)
+                    .iter()
+                    .all(|&offset| match self.lattice.column(j, offset) {
+                        Some(i) => h_new <= s[i] + self.max_step,
+                        None => true,
+                    });
+
+            if allowed {
+                s.place(j, h_new);
+                return;
+            }
+
+            self.rejected += 1;
+            if self.time_per_attempt {
+                return;
+            }
+        }
+    }
+
+    fn observables(&self, _s: &Surface) -> Vec<f64> {
+        // Rejected attempts per site, in the same units as `t`
+        vec![self.rejected as f64 / self.sites as f64]
+    }
+}
@@ -0,0 +1,64 @@
+/*
+Wolf-Villain (WV) model of molecular-beam epitaxy.
+
+A particle lands on top of column j, then moves to whichever column within `diffusion_length` steps
+of it would give it the most bonds, counting the bond to the site below and one for each nearest
+neighbour column at least as high as the particle. If column j is already (joint) best the particle
+stays put, otherwise any ties between the best neighbours are broken at random.
+
+Particles are never lost, so the volume of the deposit is conserved by the relaxation.
+*/
+
+use super::{bonds, GrowthModel};
+use crate::sim::lattice::Lattice;
+use crate::sim::random::Ran2Generator;
+use crate::sim::surface::Surface;
+use crate::sim::SimulationParams;
+
+pub struct WolfVillain {
+    diffusion_length: i32,
+    lattice: Lattice,
+}
+
+impl GrowthModel for WolfVillain {
+    fn new(params: &SimulationParams) -> WolfVillain {
+        WolfVillain {
+            diffusion_length: params.diffusion_length,
+            lattice: Lattice::new(params, params.diffusion_length.max(1)),
+        }
+    }
+
+    fn deposit(&mut self, s: &mut Surface, rng: &mut Ran2Generator) {
+        let j: usize = self.lattice.landing_site(rng); // column number
+
+        // Start at column j, which wins any ties with its neighbours
+        let mut best: usize = j;
+        let mut most_bonds: usize = bonds(s, &self.lattice, j);
+        let mut n_best: u32 = 0; // number of neighbours tied for the most bonds
+
+        // find the column with the most bonds within the diffusion length
+        for k in 1..=self.diffusion_length {
+            for &offset in self.lattice.shell(k) {
+                let i = match self.lattice.column(j, offset) {
+                    Some(i) => i,
+                    None => continue,
+                };
+
+                let n_bonds: usize = bonds(s, &self.lattice, i);
+                if n_bonds > most_bonds {
+                    best = i;
+                    most_bonds = n_bonds;
+                    n_best = 1;
+                } else if n_bonds == most_bonds && n_best > 0 {
+                    // Tied with the current best neighbour, so pick between them uniformly
+                    n_best += 1;
+                    if rng.next() * (n_best as f64) < 1.0 {
+                        best = i;
+                    }
+                }
+            }
+        }
+        // land on top of the best column
+        s.place(best, s[best] + 1);
+    }
+}
@@ -0,0 +1,176 @@
+/*
+Library file for the schedule of times at which the surface is measured.
+
+The surface is measured, and a row written to the .csv, at each time in the schedule:
+    - `legacy`: The original schedule, which drops 1 + t N / 100 particles on N columns at a time (or
+        a whole monolayer at first), so that default runs hit the same time points as older data
+    - `log`: Logarithmically spaced times from t = 1, with `points_per_decade` points in each decade
+    - `linear`: Evenly spaced times, every `interval` monolayers
+    - `explicit`: A list of times given in `times`
+    - `hybrid`: Every time from both the `log` and `linear` schedules, which samples densely at
+        early times like `log`, and keeps sampling densely in the saturated regime like `linear`
+
+`log` and `linear` schedules finish with a last time point at `t_max`, whereas `explicit` times
+beyond `t_max` are left out, so that the run finishes at the last of them. The `legacy` schedule
+keeps going until it reaches or passes `t_max`. Times are rounded to a whole number of particles, so
+times closer together than a single particle are only measured once.
+*/
+
+/*
+Enums
+*/
+
+// Names of the schedules which can be supplied in `config_sim.ini`
+pub const SCHEDULE_NAMES: [&str; 5] = ["legacy", "log", "linear", "explicit", "hybrid"];
+
+// Times at which the surface is measured
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimeSchedule {
+    Legacy,
+    Log {
+        points_per_decade: f64,
+    },
+    Linear {
+        interval: f64,
+    },
+    Explicit {
+        times: Vec<f64>,
+    },
+    Hybrid {
+        points_per_decade: f64,
+        interval: f64,
+    },
+}
+
+impl TimeSchedule {
+    // Name of the schedule, as used in `config_sim.ini`
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeSchedule::Legacy => "legacy",
+            TimeSchedule::Log { .. } => "log",
+            TimeSchedule::Linear { .. } => "linear",
+            TimeSchedule::Explicit { .. } => "explicit",
+            TimeSchedule::Hybrid { .. } => "hybrid",
+        }
+    }
+
+    // Options of the schedule, as (name, value) pairs for the run metadata
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        match self {
+            TimeSchedule::Legacy => Vec::new(),
+            TimeSchedule::Log { points_per_decade } => {
+                vec![("points_per_decade", points_per_decade.to_string())]
+            }
+            TimeSchedule::Linear { interval } => vec![("interval", interval.to_string())],
+            TimeSchedule::Explicit { times } => vec![(
+                "times",
+                times
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<String>>()
+                    .join(":"),
+            )],
+            TimeSchedule::Hybrid {
+                points_per_decade,
+                interval,
+            } => vec![
+                ("points_per_decade", points_per_decade.to_string()),
+                ("interval", interval.to_string()),
+            ],
+        }
+    }
+
+    // Label for the schedule in the output filenames, which is left out for the legacy schedule
+    pub fn label(&self) -> String {
+        match self {
+            TimeSchedule::Legacy => String::new(),
+            TimeSchedule::Log { points_per_decade } => format!("_schedlog_n{}", points_per_decade),
+            TimeSchedule::Linear { interval } => format!("_schedlinear_i{}", interval),
+            TimeSchedule::Explicit { times } => format!(
+                "_schedexplicit_{}",
+                times
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<String>>()
+                    .join("-")
+            ),
+            TimeSchedule::Hybrid {
+                points_per_decade,
+                interval,
+            } => format!("_schedhybrid_n{}_i{}", points_per_decade, interval),
+        }
+    }
+
+    // Total number of particles to have been dropped onto a substrate of `sites` columns by each
+    //  time point, in increasing order
+    pub fn deposits(&self, sites: usize, t_max: f64) -> Vec<usize> {
+        let times: Vec<f64> = match self {
+            TimeSchedule::Legacy => return legacy_deposits(sites, t_max),
+            TimeSchedule::Log { points_per_decade } => log_times(*points_per_decade, t_max),
+            TimeSchedule::Linear { interval } => linear_times(*interval, t_max),
+            TimeSchedule::Explicit { times } => {
+                times.iter().copied().filter(|&t| t <= t_max).collect()
+            }
+            TimeSchedule::Hybrid {
+                points_per_decade,
+                interval,
+            } => {
+                let mut times: Vec<f64> = log_times(*points_per_decade, t_max);
+                times.extend(linear_times(*interval, t_max));
+                times
+            }
+        };
+
+        // Round to whole particles, dropping any time which would repeat an earlier one
+        let mut deposits: Vec<usize> = times
+            .iter()
+            .map(|t| ((t * sites as f64).round() as usize).max(1))
+            .collect();
+        deposits.sort_unstable();
+        deposits.dedup();
+        deposits
+    }
+}
+
+/*
+Functions
+*/
+
+// Total number of particles dropped by each time point of the original schedule, which drops
+//  1 + t N / 100 particles on N columns at a time, apart from a whole monolayer while that would
+//  only be one
+fn legacy_deposits(sites: usize, t_max: f64) -> Vec<usize> {
+    let mut deposits: Vec<usize> = Vec::new();
+    let mut t: f64 = 0.0;
+    let mut total: usize = 0;
+    while t < t_max {
+        let mut n: usize = (t * sites as f64 / 100.0 + 1.0) as usize;
+        if n == 1 {
+            n = sites
+        }
+        t += n as f64 / sites as f64;
+        total += n;
+        deposits.push(total);
+    }
+    deposits
+}
+
+// Logarithmically spaced times from t = 1 up to `t_max`
+fn log_times(points_per_decade: f64, t_max: f64) -> Vec<f64> {
+    let mut times: Vec<f64> = (0..)
+        .map(|j| 10f64.powf(j as f64 / points_per_decade))
+        .take_while(|&t| t < t_max)
+        .collect();
+    times.push(t_max);
+    times
+}
+
+// Evenly spaced times up to `t_max`
+fn linear_times(interval: f64, t_max: f64) -> Vec<f64> {
+    let mut times: Vec<f64> = (1..)
+        .map(|j| interval * j as f64)
+        .take_while(|&t| t < t_max)
+        .collect();
+    times.push(t_max);
+    times
+}
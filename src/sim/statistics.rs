@@ -0,0 +1,258 @@
+/*
+Library file for the statistical errors of ensemble averaged quantities.
+
+Each quantity averaged over the seeds has a standard error, the sample standard deviation across the
+seeds divided by the square root of the number of seeds. Quantities derived from the averages, like
+the effective growth exponent, are nonlinear in them, so their errors are instead estimated by
+resampling the seeds and recomputing the quantity for each resampled ensemble:
+    - `jackknife`: Leave out each seed in turn, giving as many ensembles as there are seeds
+    - `bootstrap`: Draw `samples` ensembles of the same size as the original, picking seeds at random
+        with replacement
+
+The derived quantity is the local log-slope d ln(w) / d ln(t) of the averaged width, fitted by least
+squares over a window of `decades` decades of time centred on each time point. In the growth regime
+this is the effective growth exponent beta, and comparing it between runs needs its error.
+*/
+
+use super::random::Ran2Generator;
+
+/*
+Enums
+*/
+
+// Names of the ways to estimate the errors of the log-slopes which can be supplied in `config_sim.ini`
+pub const SLOPE_ERROR_NAMES: [&str; 3] = ["none", "jackknife", "bootstrap"];
+
+// How to estimate the errors of the log-slopes of the width, if at all
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlopeErrors {
+    None,
+    Jackknife,
+    Bootstrap { samples: usize },
+}
+
+impl SlopeErrors {
+    // Name of the estimator, as used in `config_sim.ini`
+    pub fn name(&self) -> &'static str {
+        match self {
+            SlopeErrors::None => "none",
+            SlopeErrors::Jackknife => "jackknife",
+            SlopeErrors::Bootstrap { .. } => "bootstrap",
+        }
+    }
+
+    // Options of the estimator, as (name, value) pairs for the run metadata
+    pub fn options(&self) -> Vec<(&'static str, String)> {
+        match self {
+            SlopeErrors::Bootstrap { samples } => vec![("bootstrap_samples", samples.to_string())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/*
+Functions
+*/
+
+// Standard error of the mean of `values`, using the sample standard deviation
+pub fn standard_error(values: &[f64]) -> f64 {
+    let n: f64 = values.len() as f64;
+    let mean: f64 = values.iter().sum::<f64>() / n;
+    let variance: f64 = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (variance / n).sqrt()
+}
+
+// Slope of ln(v) against ln(t), fitted by least squares. `None` if there aren't two distinct times
+//  to fit, or any width isn't positive
+pub fn log_slope(t: &[f64], v: &[f64]) -> Option<f64> {
+    if v.iter().any(|&v_j| v_j <= 0.0) {
+        return None;
+    }
+
+    let points: Vec<(f64, f64)> = t
+        .iter()
+        .zip(v)
+        .map(|(t_j, v_j)| (t_j.ln(), v_j.ln()))
+        .collect();
+    let n: f64 = points.len() as f64;
+    let (x_mean, y_mean): (f64, f64) = points
+        .iter()
+        .fold((0.0, 0.0), |(x, y), &(x_j, y_j)| (x + x_j / n, y + y_j / n));
+    let (sxy, sxx): (f64, f64) = points.iter().fold((0.0, 0.0), |(sxy, sxx), &(x_j, y_j)| {
+        (
+            sxy + (x_j - x_mean) * (y_j - y_mean),
+            sxx + (x_j - x_mean).powi(2),
+        )
+    });
+
+    if sxx > 0.0 {
+        Some(sxy / sxx)
+    } else {
+        None
+    }
+}
+
+// Local log-slope of the width at each time point, fitted over the time points within `decades / 2`
+//  decades either side of it
+pub fn local_slopes(t: &[f64], v: &[f64], decades: f64) -> Vec<Option<f64>> {
+    let half_window: f64 = 10f64.powf(decades / 2.0);
+    t.iter()
+        .map(|&t_j| {
+            let start: usize = t.partition_point(|&t_k| t_k < t_j / half_window);
+            let end: usize = t.partition_point(|&t_k| t_k <= t_j * half_window);
+            log_slope(&t[start..end], &v[start..end])
+        })
+        .collect()
+}
+
+// Local log-slopes of the ensemble averaged width, along with their errors estimated by resampling
+//  the widths `v_seeds` of each seed. Errors are `None` if any resampled ensemble has no slope there
+pub fn slope_errors(
+    t: &[f64],
+    v_seeds: &[&[f64]],
+    decades: f64,
+    method: SlopeErrors,
+    rng: &mut Ran2Generator,
+) -> Vec<(Option<f64>, Option<f64>)> {
+    let n: usize = v_seeds.len();
+
+    // Average width of the ensemble made up of the seeds with each index in `picks`
+    let average = |picks: &[usize]| -> Vec<f64> {
+        (0..t.len())
+            .map(|j| picks.iter().map(|&i| v_seeds[i][j]).sum::<f64>() / picks.len() as f64)
+            .collect()
+    };
+
+    let all: Vec<usize> = (0..n).collect();
+    let slopes: Vec<Option<f64>> = local_slopes(t, &average(&all), decades);
+
+    // Slopes of each resampled ensemble, and the factor which turns their spread into an error
+    let (samples, scale): (Vec<Vec<Option<f64>>>, f64) = match method {
+        SlopeErrors::None => return slopes.into_iter().map(|slope| (slope, None)).collect(),
+        SlopeErrors::Jackknife => (
+            (0..n)
+                .map(|left_out| {
+                    let picks: Vec<usize> =
+                        all.iter().copied().filter(|&i| i != left_out).collect();
+                    local_slopes(t, &average(&picks), decades)
+                })
+                .collect(),
+            (n as f64 - 1.0) / n as f64,
+        ),
+        SlopeErrors::Bootstrap { samples } => (
+            (0..samples)
+                .map(|_| {
+                    let picks: Vec<usize> = (0..n)
+                        .map(|_| ((rng.next() * n as f64) as usize).min(n - 1))
+                        .collect();
+                    local_slopes(t, &average(&picks), decades)
+                })
+                .collect(),
+            1.0 / (samples as f64 - 1.0),
+        ),
+    };
+
+    slopes
+        .iter()
+        .enumerate()
+        .map(|(j, &slope)| {
+            let resampled: Option<Vec<f64>> = samples.iter().map(|sample| sample[j]).collect();
+            let error: Option<f64> = resampled.map(|resampled| {
+                let mean: f64 = resampled.iter().sum::<f64>() / resampled.len() as f64;
+                (scale * resampled.iter().map(|s| (s - mean).powi(2)).sum::<f64>()).sqrt()
+            });
+            (slope, error)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jackknife_slope_of_exact_power_law() {
+        // Every seed grows as a t^(1/3), with a different prefactor a, so every resampled ensemble
+        //  has exactly the same log-slope
+        let t: Vec<f64> = (0..40).map(|j| 10f64.powf(j as f64 / 10.0)).collect();
+        let v_seeds: Vec<Vec<f64>> = [0.5, 1.0, 2.0, 3.5]
+            .iter()
+            .map(|a| t.iter().map(|t_j| a * t_j.powf(1.0 / 3.0)).collect())
+            .collect();
+        let v_refs: Vec<&[f64]> = v_seeds.iter().map(|v| &v[..]).collect();
+        let mut rng: Ran2Generator = Ran2Generator::new(-1);
+
+        let slopes = slope_errors(&t, &v_refs, 0.5, SlopeErrors::Jackknife, &mut rng);
+        assert_eq!(slopes.len(), t.len());
+        for (slope, error) in slopes {
+            assert!((slope.unwrap() - 1.0 / 3.0).abs() < 1e-12);
+            assert!(error.unwrap() < 1e-12);
+        }
+    }
+
+    // Three seeds of width 1 at t = 1, whose widths at t = b are chosen so that leaving out each seed
+    //  in turn gives an average width of 1, b or b^2 there, and so log-slopes of exactly 0, 1 and 2
+    fn resampling_ensemble(b: f64) -> (Vec<f64>, Vec<Vec<f64>>) {
+        let total: f64 = 1.0 + b + b * b;
+        let v_seeds: Vec<Vec<f64>> = [1.0, b, b * b]
+            .iter()
+            .map(|left_out_mean| vec![1.0, total - 2.0 * left_out_mean])
+            .collect();
+        (vec![1.0, b], v_seeds)
+    }
+
+    #[test]
+    fn jackknife_error_of_known_spread() {
+        let b: f64 = 1.2;
+        let (t, v_seeds) = resampling_ensemble(b);
+        let v_refs: Vec<&[f64]> = v_seeds.iter().map(|v| &v[..]).collect();
+        let mut rng: Ran2Generator = Ran2Generator::new(-1);
+
+        // The leave-one-out slopes 0, 1 and 2 have a mean of 1, so the jackknife variance is
+        //  (n - 1) / n * ((0 - 1)^2 + (1 - 1)^2 + (2 - 1)^2) = 2 / 3 * 2
+        let slope: f64 = ((1.0 + b + b * b) / 3.0).ln() / b.ln();
+        let error: f64 = (4.0f64 / 3.0).sqrt();
+        let slopes = slope_errors(&t, &v_refs, 1.0, SlopeErrors::Jackknife, &mut rng);
+        for (actual_slope, actual_error) in slopes {
+            assert!((actual_slope.unwrap() - slope).abs() < 1e-12);
+            assert!((actual_error.unwrap() - error).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn bootstrap_error_with_fixed_seed() {
+        let b: f64 = 1.2;
+        let (t, v_seeds) = resampling_ensemble(b);
+        let v_refs: Vec<&[f64]> = v_seeds.iter().map(|v| &v[..]).collect();
+        let samples: usize = 5;
+
+        // Redraw the same resampled ensembles from the same seed. Every width at t = 1 is 1, so the
+        //  slope of each ensemble is just the log of its average width at t = b, over ln(b)
+        let mut rng: Ran2Generator = Ran2Generator::new(-11);
+        let resampled: Vec<f64> = (0..samples)
+            .map(|_| {
+                let v_b: f64 = (0..3)
+                    .map(|_| v_seeds[((rng.next() * 3.0) as usize).min(2)][1])
+                    .sum::<f64>()
+                    / 3.0;
+                v_b.ln() / b.ln()
+            })
+            .collect();
+        let mean: f64 = resampled.iter().sum::<f64>() / samples as f64;
+        let variance: f64 =
+            resampled.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (samples as f64 - 1.0);
+        assert!(variance > 0.0);
+
+        let mut rng: Ran2Generator = Ran2Generator::new(-11);
+        let slopes = slope_errors(
+            &t,
+            &v_refs,
+            1.0,
+            SlopeErrors::Bootstrap { samples },
+            &mut rng,
+        );
+        for (_slope, error) in slopes {
+            assert!((error.unwrap() - variance.sqrt()).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/*
+Library file for the structure factor of the surface.
+
+The structure factor S(k, t) = |h_k|^2 / N is the power spectrum of the surface, where h_k is the
+Fourier transform of h(x) - <h> over the N columns of the substrate, at wavenumber q = 2 pi k / L.
+Below the correlation length, it falls off as S(k) ~ k^-(d + 2 alpha), which gives much cleaner
+estimates of the roughness exponent alpha than the real-space width on large substrates. On a 2D
+substrate, S is averaged over every wavevector whose length rounds to the same k.
+*/
+
+use super::fft::{fft_nd, wavenumber, Complex};
+
+/*
+Functions
+*/
+
+// Structure factor S(k) for each k from 1 to L/2, for a surface `s` on an L x L (or just L, in 1D)
+//  substrate
+pub fn structure_factor(s: &[usize], l: usize, dimension: usize) -> Vec<f64> {
+    let sites: usize = s.len();
+    let mean: f64 = s.iter().sum::<usize>() as f64 / sites as f64;
+    let mut modes: Vec<Complex> = s.iter().map(|&h| (h as f64 - mean, 0.0)).collect();
+    fft_nd(&mut modes, l, dimension, false);
+
+    // Average |h_k|^2 / N over each shell of wavevectors
+    let mut power: Vec<f64> = vec![0.0; l / 2];
+    let mut modes_in_shell: Vec<usize> = vec![0; l / 2];
+    for (site, &(re, im)) in modes.iter().enumerate() {
+        let k2: f64 = wavenumber(site % l, l).powi(2)
+            + if dimension == 2 {
+                wavenumber(site / l, l).powi(2)
+            } else {
+                0.0
+            };
+        let k: usize = k2.sqrt().round() as usize;
+        if (1..=l / 2).contains(&k) {
+            power[k - 1] += (re * re + im * im) / sites as f64;
+            modes_in_shell[k - 1] += 1;
+        }
+    }
+    power
+        .iter()
+        .zip(&modes_in_shell)
+        .map(|(&p, &m)| if m > 0 { p / m as f64 } else { 0.0 })
+        .collect()
+}
@@ -0,0 +1,408 @@
+/*
+Library file defining the surface onto which particles are deposited.
+
+The surface is read just like the old `s: Vec<usize>` of column heights, but every new site has to be
+filled through `Surface::place`, so that anything else we want to know about the deposit can be kept
+up to date alongside the heights.
+
+Optionally, the surface also tracks the bulk of the deposit underneath it, which is needed to know
+where the top of a column falls to when its top particle is removed. Rather than storing every
+site, each column keeps a list of the gaps (runs of empty sites) below its top, which is far smaller
+for dense films. Connected gaps in neighbouring columns make up the voids in the film. The species of
+every deposited site is kept in the same way, as a list of the heights in each column at which the
+species changes, so the species of a particle is still known once the particles above it are gone.
+*/
+
+use std::collections::BTreeMap;
+use std::ops::Deref;
+
+use super::lattice::Lattice;
+
+// Number of particle species which can be told apart in the deposit
+pub const N_SPECIES: usize = 2;
+
+pub struct Surface {
+    heights: Vec<usize>,
+    substrate: usize, // number of sites filled by the initial substrate
+    base: Vec<usize>, // height of the initial substrate in each column
+    species: usize,   // species of the particle currently being deposited
+    top_species: Vec<Option<usize>>, // species of the top particle in each column, if any
+    deposited: [u64; N_SPECIES], // total number of sites filled by each species
+    gaps: Option<Vec<Vec<(usize, usize)>>>, // first and last empty site of each gap in each column
+    runs: Option<Vec<Vec<(usize, usize)>>>, // lowest site and species of each run in each column
+}
+
+impl Surface {
+    // Start from the initial heights of the substrate, which is solid all the way down
+    pub fn new(heights: Vec<usize>, track_bulk: bool) -> Surface {
+        let length: usize = heights.len();
+        Surface {
+            substrate: heights.iter().sum(),
+            base: heights.clone(),
+            heights,
+            species: 0,
+            top_species: vec![None; length],
+            deposited: [0; N_SPECIES],
+            gaps: if track_bulk {
+                Some(vec![Vec::new(); length])
+            } else {
+                None
+            },
+            runs: if track_bulk {
+                Some(vec![Vec::new(); length])
+            } else {
+                None
+            },
+        }
+    }
+
+    // Set the species of the particle(s) deposited from now on
+    pub fn set_species(&mut self, species: usize) {
+        self.species = species;
+    }
+
+    // Fill the site at height `h` in column `c` with the current species
+    pub fn place(&mut self, c: usize, h: usize) {
+        if let Some(gaps) = &mut self.gaps {
+            fill_gap(&mut gaps[c], self.heights[c], h);
+        }
+        if let Some(runs) = &mut self.runs {
+            record_species(&mut runs[c], self.heights[c], h, self.species);
+        }
+        if h >= self.heights[c] {
+            self.heights[c] = h;
+            self.top_species[c] = Some(self.species);
+        }
+        self.deposited[self.species] += 1;
+    }
+
+    // Whether column `c` has a deposited particle on top which can be removed, rather than just the
+    //  initial substrate
+    pub fn removable(&self, c: usize) -> bool {
+        self.heights[c] > self.base[c]
+    }
+
+    // Remove the top particle of column `c`, so the column falls to the next filled site below it.
+    //  Without the bulk, the species of the newly exposed particle isn't known, so it's left unset.
+    //  The initial substrate is never removed
+    pub fn remove(&mut self, c: usize) {
+        if !self.removable(c) {
+            return;
+        }
+        let h: usize = self.heights[c];
+        let top: usize = match self.gaps.as_mut().map(|gaps| &mut gaps[c]) {
+            Some(gaps) if gaps.last().is_some_and(|&(_, end)| end == h - 1) => {
+                gaps.pop().unwrap().0 - 1
+            }
+            _ => h - 1,
+        };
+        self.heights[c] = top;
+        if let Some(species) = self.top_species[c] {
+            self.deposited[species] = self.deposited[species].saturating_sub(1);
+        }
+        self.top_species[c] = match self.runs.as_mut().map(|runs| &mut runs[c]) {
+            Some(runs) => {
+                // Drop any runs which no longer hold a deposited site
+                while runs.last().is_some_and(|&(start, _)| start > top) {
+                    runs.pop();
+                }
+                runs.last().map(|&(_, species)| species)
+            }
+            None => None,
+        };
+    }
+
+    // Fraction of all the sites filled so far which were filled by `species`
+    pub fn fraction_deposited(&self, species: usize) -> f64 {
+        let total: u64 = self.deposited.iter().sum();
+        if total > 0 {
+            self.deposited[species] as f64 / total as f64
+        } else {
+            0.0
+        }
+    }
+
+    // Fraction of the columns whose top particle is of `species`
+    pub fn fraction_at_surface(&self, species: usize) -> f64 {
+        let count: usize = self
+            .top_species
+            .iter()
+            .zip(self.heights.iter())
+            .filter(|&(&top, &h)| top == Some(species) && h > 0)
+            .count();
+        count as f64 / self.heights.len() as f64
+    }
+
+    // Fraction of the sites between the substrate and the surface which are filled, i.e. the bulk
+    //  density of the film
+    pub fn density(&self) -> f64 {
+        let filled: u64 = self.deposited.iter().sum();
+        let volume: usize = self
+            .heights
+            .iter()
+            .sum::<usize>()
+            .saturating_sub(self.substrate);
+        if volume > 0 {
+            filled as f64 / volume as f64
+        } else {
+            1.0
+        }
+    }
+
+    // Number of voids of each size in the bulk, where a void is a set of empty sites below the
+    //  surface connected through the faces of neighbouring sites. Sets which open sideways onto the
+    //  air above a lower neighbouring column are pockets under an overhang rather than voids, so
+    //  they're left out. Empty if the bulk isn't tracked
+    pub fn void_sizes(&self, lattice: &Lattice) -> BTreeMap<usize, u64> {
+        let mut sizes: BTreeMap<usize, u64> = BTreeMap::new();
+        let gaps = match &self.gaps {
+            Some(gaps) => gaps,
+            None => return sizes,
+        };
+
+        // Number every gap, so that gaps can be joined into voids with a union-find
+        let mut first: Vec<usize> = Vec::with_capacity(gaps.len() + 1);
+        first.push(0);
+        for column in gaps {
+            first.push(first.last().unwrap() + column.len());
+        }
+        let mut parent: Vec<usize> = (0..*first.last().unwrap()).collect();
+
+        // Join gaps which overlap in neighbouring columns. Gaps in each column are in order of height,
+        //  so the overlaps between two columns can be found in a single pass over both. A gap whose
+        //  top is above the top of a neighbouring column opens onto the air there
+        let mut open: Vec<bool> = vec![false; parent.len()];
+        for (c, column) in gaps.iter().enumerate() {
+            for &offset in lattice.shell(1) {
+                let n: usize = match lattice.column(c, offset) {
+                    Some(n) if n != c => n,
+                    _ => continue,
+                };
+                for (a, &(_, end)) in column.iter().enumerate() {
+                    if end > self.heights[n] {
+                        open[first[c] + a] = true;
+                    }
+                }
+                let (mut a, mut b) = (0, 0);
+                while a < column.len() && b < gaps[n].len() {
+                    let (start_a, end_a) = column[a];
+                    let (start_b, end_b) = gaps[n][b];
+                    if start_a <= end_b && start_b <= end_a {
+                        union(&mut parent, first[c] + a, first[n] + b);
+                    }
+                    if end_a < end_b {
+                        a += 1;
+                    } else {
+                        b += 1;
+                    }
+                }
+            }
+        }
+
+        // Add up the size of each void, leaving out any set of gaps with an open gap in it
+        let mut volumes: BTreeMap<usize, (usize, bool)> = BTreeMap::new();
+        for (c, column) in gaps.iter().enumerate() {
+            for (a, &(start, end)) in column.iter().enumerate() {
+                let root: usize = find(&mut parent, first[c] + a);
+                let (volume, is_open) = volumes.entry(root).or_insert((0, false));
+                *volume += end - start + 1;
+                *is_open |= open[first[c] + a];
+            }
+        }
+        for &(volume, is_open) in volumes.values() {
+            if !is_open {
+                *sizes.entry(volume).or_insert(0) += 1;
+            }
+        }
+        sizes
+    }
+}
+
+impl Deref for Surface {
+    type Target = [usize];
+
+    fn deref(&self) -> &[usize] {
+        &self.heights
+    }
+}
+
+/*
+Functions
+*/
+
+// Update the gaps in a column of height `top` when the site at height `h` is filled, either leaving a
+//  new gap below a site placed above the top of the column, or filling in a site within a gap
+fn fill_gap(gaps: &mut Vec<(usize, usize)>, top: usize, h: usize) {
+    if h > top + 1 {
+        gaps.push((top + 1, h - 1));
+    } else if h <= top {
+        if let Some(i) = gaps.iter().position(|&(start, end)| start <= h && h <= end) {
+            let (start, end) = gaps.remove(i);
+            if h < end {
+                gaps.insert(i, (h + 1, end));
+            }
+            if start < h {
+                gaps.insert(i, (start, h - 1));
+            }
+        }
+    }
+}
+
+// Update the runs of each species in a column of height `top` when the site at height `h` is filled
+//  with `species`, splitting the run it falls in if it fills a gap within a run of another species
+fn record_species(runs: &mut Vec<(usize, usize)>, top: usize, h: usize, species: usize) {
+    // Index of the first run starting above `h`, so the site falls in the run before it
+    let i: usize = runs.partition_point(|&(start, _)| start <= h);
+    let below: Option<usize> = if i > 0 { Some(runs[i - 1].1) } else { None };
+    if below == Some(species) {
+        return;
+    }
+    let next_start: usize = runs.get(i).map_or(top + 1, |&(start, _)| start);
+    if let Some(below) = below {
+        if h + 1 < next_start {
+            runs.insert(i, (h + 1, below));
+        }
+    }
+    runs.insert(i, (h, species));
+}
+
+// Root of the set containing `i`, flattening the path to it along the way
+fn find(parent: &mut [usize], i: usize) -> usize {
+    let mut root: usize = i;
+    while parent[root] != root {
+        root = parent[root];
+    }
+    let mut i: usize = i;
+    while parent[i] != root {
+        let next: usize = parent[i];
+        parent[i] = root;
+        i = next;
+    }
+    root
+}
+
+// Join the sets containing `i` and `j`
+fn union(parent: &mut [usize], i: usize, j: usize) {
+    let (root_i, root_j) = (find(parent, i), find(parent, j));
+    if root_i != root_j {
+        parent[root_i] = root_j;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::models::ModelKind;
+    use crate::sim::SimulationParams;
+
+    // Fill every site from just above the top of column `c` up to height `h`
+    fn fill_to(s: &mut Surface, c: usize, h: usize) {
+        for y in s[c] + 1..=h {
+            s.place(c, y);
+        }
+    }
+
+    #[test]
+    fn filling_a_gap_merges_the_runs_either_side() {
+        let mut gaps: Vec<(usize, usize)> = Vec::new();
+        fill_gap(&mut gaps, 0, 1);
+        assert!(gaps.is_empty());
+
+        // A site placed at 5 on a column of height 1 leaves the gap 2..=4 below it
+        fill_gap(&mut gaps, 1, 5);
+        assert_eq!(gaps, vec![(2, 4)]);
+
+        // Filling the middle of the gap splits it in two, and filling the last site of each merges
+        //  the filled runs either side of it into one
+        fill_gap(&mut gaps, 5, 3);
+        assert_eq!(gaps, vec![(2, 2), (4, 4)]);
+        fill_gap(&mut gaps, 5, 4);
+        assert_eq!(gaps, vec![(2, 2)]);
+        fill_gap(&mut gaps, 5, 2);
+        assert!(gaps.is_empty());
+    }
+
+    #[test]
+    fn removing_the_top_particle_falls_through_the_gap_below() {
+        let mut s: Surface = Surface::new(vec![1, 0], true);
+        s.place(0, 2);
+        s.place(0, 5);
+        assert_eq!(s.gaps.as_ref().unwrap()[0], vec![(3, 4)]);
+
+        // The top falls past the gap to the next filled site, and the gap is no longer in the bulk
+        s.remove(0);
+        assert_eq!(s[0], 2);
+        assert!(s.gaps.as_ref().unwrap()[0].is_empty());
+
+        // The initial substrate is never removed
+        s.remove(0);
+        assert_eq!(s[0], 1);
+        s.remove(0);
+        assert_eq!(s[0], 1);
+        assert!(!s.removable(0));
+    }
+
+    #[test]
+    fn void_sizes_of_hand_built_columns() {
+        let params: SimulationParams = SimulationParams::for_tests(ModelKind::Ballistic, 3);
+        let lattice: Lattice = Lattice::new(&params, 1);
+        let mut s: Surface = Surface::new(vec![0; 3], true);
+
+        // Column 0 is solid up to 5. Column 1 has gaps at 2..=3 and 5, and column 2 a gap at 2,
+        //  which overlaps the lower gap in column 1
+        fill_to(&mut s, 0, 5);
+        s.place(1, 1);
+        s.place(1, 4);
+        s.place(1, 6);
+        s.place(2, 1);
+        s.place(2, 3);
+        fill_to(&mut s, 2, 5);
+        assert_eq!(s.gaps.as_ref().unwrap()[1], vec![(2, 3), (5, 5)]);
+        assert_eq!(s.gaps.as_ref().unwrap()[2], vec![(2, 2)]);
+        assert_eq!(s.void_sizes(&lattice), BTreeMap::from([(1, 1), (3, 1)]));
+        assert!((s.density() - 12.0 / 16.0).abs() < 1e-12);
+
+        // Once column 0 is worn down to 4, the upper gap in column 1 opens onto the air above it,
+        //  and once it's worn down to 2, so does the larger void
+        s.remove(0);
+        assert_eq!(s.void_sizes(&lattice), BTreeMap::from([(3, 1)]));
+        s.remove(0);
+        s.remove(0);
+        assert!(s.void_sizes(&lattice).is_empty());
+    }
+
+    #[test]
+    fn species_are_recorded_through_fills_and_removals() {
+        let mut s: Surface = Surface::new(vec![0; 3], true);
+        let deposits: [(usize, usize, usize); 8] = [
+            (0, 0, 1),
+            (1, 0, 2),
+            (1, 1, 1),
+            (0, 1, 3),
+            (1, 1, 2),
+            (0, 2, 1),
+            (0, 2, 4),
+            (1, 2, 2),
+        ];
+        for (species, c, h) in deposits {
+            s.set_species(species);
+            s.place(c, h);
+        }
+
+        // Filling the gap in column 2 with species 1 splits the run of species 0 around it
+        assert_eq!(s.deposited, [4, 4]);
+        assert_eq!(s.runs.as_ref().unwrap()[1], vec![(1, 1), (3, 0)]);
+        assert_eq!(s.runs.as_ref().unwrap()[2], vec![(1, 0), (2, 1), (3, 0)]);
+        assert!((s.fraction_deposited(0) - 0.5).abs() < 1e-12);
+        assert!((s.fraction_at_surface(0) - 2.0 / 3.0).abs() < 1e-12);
+        assert!((s.fraction_at_surface(1) - 1.0 / 3.0).abs() < 1e-12);
+
+        // Removing the top of column 2 exposes the particle of species 1 below it
+        s.remove(2);
+        assert_eq!(s[2], 2);
+        assert_eq!(s.top_species[2], Some(1));
+        assert_eq!(s.deposited, [3, 4]);
+        assert!((s.fraction_deposited(0) - 3.0 / 7.0).abs() < 1e-12);
+        assert!((s.fraction_at_surface(1) - 2.0 / 3.0).abs() < 1e-12);
+    }
+}